@@ -2,102 +2,309 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    from_binary, to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn,
+    Response, StdError, SubMsg, WasmMsg,
 };
 
 use cw20::Cw20ReceiveMsg;
 use olympus_pro::{
-    custom_bond::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, State},
-    querier::{query_decimals, query_token_decimals},
+    bonding_calculator::BondingCalculator,
+    custom_bond::{
+        Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, State, TermsChange,
+    },
+    note_nft::InstantiateMsg as NoteNftInstantiateMsg,
+    ownership::{query_proposal, KEY_POLICY_PROPOSAL},
+    querier::{query_decimals, query_pair_info},
+    response::MsgInstantiateContractResponse,
+    utils::validate_fee_tiers,
 };
-use terraswap::asset::AssetInfoRaw;
+use protobuf::Message;
+use terraswap::asset::{AssetInfo, AssetInfoRaw};
 
 use crate::{
+    error::ContractError,
     execute::{
-        deposit, initialize_bond, pay_subsidy, redeem, set_adjustment, set_bond_terms,
-        update_olympus_treasury, update_policy,
+        accept_olympus_dao, accept_policy, apply_pending_terms, approve_depositor,
+        cancel_olympus_dao_proposal, cancel_pending_terms, cancel_proposal, deposit, deposit_from,
+        deposit_received, initialize_bond, pay_subsidy, propose_new_olympus_dao,
+        propose_new_policy, redeem, revoke_depositor, schedule_terms_change, set_paused,
+        store_initial_terms, sweep_principal, update_fee_recipients, update_fee_tiers,
+        update_olympus_treasury, update_pauser, update_price_guard,
     },
     query::{
-        query_bond_info, query_bond_price, query_config, query_current_debt,
-        query_current_olympus_fee, query_custom_treasury_config, query_payout_for, query_state,
+        query_all_bond_infos, query_bond_info, query_bond_price, query_bond_summary, query_config,
+        query_current_debt, query_current_olympus_fee, query_custom_treasury_config,
+        query_depositor_approval, query_note, query_payout_for, query_pending_terms,
+        query_referrer, query_simulate_deposit, query_state,
     },
-    state::{read_config, store_config, store_state, Config},
-    utils::get_received_native_fund,
+    state::{read_config, store_config, store_state, Config, KEY_OLYMPUS_DAO_PROPOSAL},
+    utils::{get_received_native_fund, price_guard_to_raw},
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let custom_treasury_config =
         query_custom_treasury_config(&deps.querier, msg.custom_treasury.clone())?;
 
-    let payout_decimals =
-        query_token_decimals(&deps.querier, custom_treasury_config.payout_token.clone())?;
+    let payout_decimals = query_decimals(&deps.querier, &custom_treasury_config.payout_token)?;
     let principal_decimals = query_decimals(&deps.querier, &msg.principal_token)?;
 
+    let bonding_calculator = msg
+        .bonding_calculator
+        .unwrap_or(BondingCalculator::Reserve {});
+    if let BondingCalculator::TerraswapLp { pair } = bonding_calculator.clone() {
+        let pair_info = query_pair_info(&deps.querier, pair)?;
+        if msg.principal_token
+            != (AssetInfo::Token {
+                contract_addr: pair_info.liquidity_token,
+            })
+        {
+            return Err(ContractError::PrincipalNotLiquidityToken {});
+        }
+    }
+
+    validate_fee_tiers(&msg.fee_tiers)?;
+
+    let price_guard = price_guard_to_raw(
+        deps.as_ref(),
+        msg.price_guard,
+        &custom_treasury_config.payout_token,
+        &msg.principal_token,
+        &bonding_calculator,
+    )?;
+
     store_config(
         deps.storage,
         &Config {
             custom_treasury: deps.api.addr_canonicalize(&msg.custom_treasury)?,
-            payout_token: deps
-                .api
-                .addr_canonicalize(&custom_treasury_config.payout_token)?,
+            payout_token: custom_treasury_config.payout_token.to_raw(deps.api)?,
             principal_token: msg.principal_token.to_raw(deps.api)?,
             olympus_treasury: deps.api.addr_canonicalize(&msg.olympus_treasury)?,
             subsidy_router: deps.api.addr_canonicalize(&msg.subsidy_router)?,
             policy: deps.api.addr_canonicalize(&msg.initial_owner)?,
+            pauser: deps
+                .api
+                .addr_canonicalize(msg.pauser.as_ref().unwrap_or(&msg.initial_owner))?,
             olympus_dao: deps.api.addr_canonicalize(&msg.olympus_dao)?,
             fee_tiers: msg.fee_tiers,
             fee_in_payout: msg.fee_in_payout,
             payout_decimals,
             principal_decimals,
+            principal_recipient: deps
+                .api
+                .addr_canonicalize(&msg.principal_recipient.unwrap_or(msg.custom_treasury))?,
+            note_nft: None,
+            price_guard,
+            bonding_calculator: bonding_calculator.to_raw(deps.api)?,
+            terms_delay: msg.terms_delay.unwrap_or_default(),
+            fee_recipients: vec![],
+            referrer_share: Decimal::zero(),
+            measure_received_balance: msg.measure_received_balance.unwrap_or_default(),
+            fee_recipient_admin: match msg.fee_recipient_admin {
+                Some(fee_recipient_admin) => {
+                    Some(deps.api.addr_canonicalize(&fee_recipient_admin)?)
+                }
+                None => None,
+            },
         },
     )?;
 
     store_state(deps.storage, &State::default())?;
 
+    if let Some(terms) = msg.terms {
+        store_initial_terms(
+            deps.storage,
+            env.block.time.seconds(),
+            terms,
+            msg.initial_debt.unwrap_or_default(),
+        )?;
+    }
+
+    if let Some(note_nft_code_id) = msg.note_nft_code_id {
+        return Ok(Response::new().add_submessage(SubMsg {
+            id: 1,
+            gas_limit: None,
+            msg: WasmMsg::Instantiate {
+                code_id: note_nft_code_id,
+                funds: vec![],
+                admin: Some(env.contract.address.to_string()),
+                label: "OlympusPro Bond Note".to_string(),
+                msg: to_binary(&NoteNftInstantiateMsg {
+                    name: "OlympusPro Bond Note".to_string(),
+                    symbol: "OPBN".to_string(),
+                    minter: env.contract.address.to_string(),
+                })?,
+            }
+            .into(),
+            reply_on: ReplyOn::Success,
+        }));
+    }
+
     Ok(Response::default())
 }
 
+/// Stores the address of the note NFT contract instantiated at bond creation, and bonds the
+/// principal received by a `DepositFrom` transfer
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        1 => {
+            let data = msg
+                .result
+                .into_result()
+                .map_err(StdError::generic_err)?
+                .data
+                .ok_or(ContractError::MissingInstantiateData {})?;
+            let res: MsgInstantiateContractResponse = Message::parse_from_bytes(data.as_slice())
+                .map_err(|_| {
+                    StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+                })?;
+
+            let mut config = read_config(deps.storage)?;
+            config.note_nft = Some(deps.api.addr_canonicalize(res.get_contract_address())?);
+            store_config(deps.storage, &config)?;
+
+            Ok(Response::new().add_attributes(vec![
+                ("action", "register_note_nft"),
+                ("note_nft", res.get_contract_address()),
+            ]))
+        }
+        2 => deposit_received(deps, env),
+        id => Err(ContractError::InvalidReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::Deposit {
             max_price,
             depositor,
+            allow_partial,
+            min_payout,
+            deadline,
+            referrer,
         } => {
+            let sender = info.sender.to_string();
             let amount = get_received_native_fund(deps.storage, info)?;
-            deposit(deps, env, amount, max_price, depositor)
+            deposit(
+                deps,
+                env,
+                sender,
+                amount,
+                max_price,
+                depositor,
+                allow_partial,
+                min_payout,
+                deadline,
+                referrer,
+            )
         }
-        ExecuteMsg::Redeem {} => redeem(deps, env, info.sender.to_string()),
+        ExecuteMsg::DepositFrom {
+            amount,
+            max_price,
+            depositor,
+            allow_partial,
+            min_payout,
+            deadline,
+            referrer,
+        } => deposit_from(
+            deps,
+            env,
+            info.sender.to_string(),
+            amount,
+            Cw20HookMsg::Deposit {
+                max_price,
+                depositor,
+                allow_partial,
+                min_payout,
+                deadline,
+                referrer,
+            },
+        ),
+        ExecuteMsg::Redeem { note_ids } => redeem(deps, env, info.sender.to_string(), note_ids),
+        ExecuteMsg::ApproveDepositor { operator } => approve_depositor(deps, info, operator),
+        ExecuteMsg::RevokeDepositor { operator } => revoke_depositor(deps, info, operator),
         ExecuteMsg::PaySubsidy {} => pay_subsidy(deps, info),
         ExecuteMsg::UpdateOlympusTreasury { olympus_treasury } => {
             update_olympus_treasury(deps, info, olympus_treasury)
         }
+        ExecuteMsg::UpdateFeeTiers { fee_tiers } => update_fee_tiers(deps, info, fee_tiers),
+        ExecuteMsg::UpdateFeeRecipients {
+            fee_recipients,
+            referrer_share,
+        } => update_fee_recipients(deps, info, fee_recipients, referrer_share),
+        ExecuteMsg::AcceptPolicy {} => accept_policy(deps, env, info),
+        ExecuteMsg::ProposeNewOlympusDao {
+            olympus_dao,
+            expires,
+        } => propose_new_olympus_dao(deps, env, info, olympus_dao, expires),
+        ExecuteMsg::AcceptOlympusDao {} => accept_olympus_dao(deps, env, info),
+        ExecuteMsg::CancelOlympusDaoProposal {} => cancel_olympus_dao_proposal(deps, info),
+        ExecuteMsg::ApplyPendingTerms {} => apply_pending_terms(deps, env),
+        ExecuteMsg::Pause {} => set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => set_paused(deps, info, false),
         _ => {
             assert_policy_privilege(deps.as_ref(), info)?;
             match msg {
-                ExecuteMsg::UpdatePolicy { policy } => update_policy(deps, policy),
+                ExecuteMsg::ProposeNewPolicy { policy, expires } => {
+                    propose_new_policy(deps, env, policy, expires)
+                }
+                ExecuteMsg::CancelProposal {} => cancel_proposal(deps),
+                ExecuteMsg::UpdatePauser { pauser } => update_pauser(deps, pauser),
+                ExecuteMsg::UpdatePriceGuard { price_guard } => {
+                    update_price_guard(deps, price_guard)
+                }
+                ExecuteMsg::SweepPrincipal {} => sweep_principal(deps, env),
                 ExecuteMsg::InitializeBond {
                     terms,
                     initial_debt,
                 } => initialize_bond(deps, env, terms, initial_debt),
                 ExecuteMsg::SetBondTerms {
                     vesting_term,
+                    vesting_end,
                     max_payout,
                     max_debt,
-                } => set_bond_terms(deps, vesting_term, max_payout, max_debt),
+                } => schedule_terms_change(
+                    deps,
+                    env,
+                    TermsChange::BondTerms {
+                        vesting_term,
+                        vesting_end,
+                        max_payout,
+                        max_debt,
+                    },
+                ),
                 ExecuteMsg::SetAdjustment {
                     addition,
                     increment,
                     target,
                     buffer,
-                } => set_adjustment(deps, env, addition, increment, target, buffer),
+                } => schedule_terms_change(
+                    deps,
+                    env,
+                    TermsChange::Adjustment {
+                        addition,
+                        increment,
+                        target,
+                        buffer,
+                    },
+                ),
+                ExecuteMsg::SetTermsDelay { terms_delay } => {
+                    schedule_terms_change(deps, env, TermsChange::TermsDelay { terms_delay })
+                }
+                ExecuteMsg::CancelPendingTerms { id } => cancel_pending_terms(deps, id),
                 _ => panic!("do not enter here"),
             }
         }
@@ -105,8 +312,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::State {} => to_binary(&query_state(deps)?),
         QueryMsg::BondPrice {} => to_binary(&query_bond_price(deps, env)?),
@@ -114,11 +321,42 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::CurrentDebt {} => to_binary(&query_current_debt(deps, env)?),
         QueryMsg::CurrentOlympusFee {} => to_binary(&query_current_olympus_fee(deps)?),
         QueryMsg::BondInfo { user } => to_binary(&query_bond_info(deps, env, user)?),
-    }
+        QueryMsg::AllBondInfos {
+            start_after,
+            start_after_note_id,
+            limit,
+        } => to_binary(&query_all_bond_infos(
+            deps,
+            env,
+            start_after,
+            start_after_note_id,
+            limit,
+        )?),
+        QueryMsg::BondSummary {} => to_binary(&query_bond_summary(deps)?),
+        QueryMsg::Note { note_id } => to_binary(&query_note(deps, env, note_id)?),
+        QueryMsg::DepositorApproval { user, operator } => {
+            to_binary(&query_depositor_approval(deps, user, operator)?)
+        }
+        QueryMsg::SimulateDeposit {
+            amount,
+            depositor,
+            sender,
+        } => to_binary(&query_simulate_deposit(
+            deps, env, amount, depositor, sender,
+        )?),
+        QueryMsg::PolicyProposal {} => to_binary(&query_proposal(deps, KEY_POLICY_PROPOSAL)?),
+        QueryMsg::OlympusDaoProposal {} => {
+            to_binary(&query_proposal(deps, KEY_OLYMPUS_DAO_PROPOSAL)?)
+        }
+        QueryMsg::PendingTerms {} => to_binary(&query_pending_terms(deps)?),
+        QueryMsg::Referrer { referrer } => to_binary(&query_referrer(deps, referrer)?),
+    };
+
+    Ok(res?)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     Ok(Response::default())
 }
 
@@ -127,26 +365,44 @@ pub fn receive_cw20(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::Deposit {
             max_price,
             depositor,
+            allow_partial,
+            min_payout,
+            deadline,
+            referrer,
         } => {
             let config = read_config(deps.storage)?;
+            if config.measure_received_balance {
+                return Err(ContractError::ReceivedBalanceNotMeasured {});
+            }
             if let AssetInfoRaw::Token { contract_addr } = config.principal_token {
                 if deps.api.addr_humanize(&contract_addr)? == info.sender.clone() {
-                    return deposit(deps, env, cw20_msg.amount, max_price, depositor);
+                    return deposit(
+                        deps,
+                        env,
+                        cw20_msg.sender,
+                        cw20_msg.amount,
+                        max_price,
+                        depositor,
+                        allow_partial,
+                        min_payout,
+                        deadline,
+                        referrer,
+                    );
                 }
             }
-            Err(StdError::generic_err("invalid cw20 token"))
+            Err(ContractError::InvalidCw20Token {})
         }
     }
 }
 
-fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> StdResult<()> {
+fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> Result<(), ContractError> {
     if read_config(deps.storage)?.policy != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     Ok(())
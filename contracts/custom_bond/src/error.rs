@@ -0,0 +1,211 @@
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
+use olympus_pro::{
+    bonding_calculator::BondingCalculatorError, custom_bond::DepositFailure,
+    ownership::OwnershipError, utils::FeeTierError,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    FeeTier(#[from] FeeTierError),
+
+    #[error("{0}")]
+    BondingCalculator(#[from] BondingCalculatorError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("only subsidy controller")]
+    OnlySubsidyController {},
+
+    #[error("invalid reply id: {id}")]
+    InvalidReplyId { id: u64 },
+
+    #[error("missing instantiate data")]
+    MissingInstantiateData {},
+
+    #[error("principal must be the liquidity token of the pair")]
+    PrincipalNotLiquidityToken {},
+
+    #[error("invalid price guard discount band")]
+    InvalidPriceGuardDiscount {},
+
+    #[error("price guard pair must trade payout against principal")]
+    InvalidPriceGuardPair {},
+
+    #[error("price guard cannot be combined with the lp bonding calculator")]
+    PriceGuardWithLpCalculator {},
+
+    #[error("debt must be 0 for initialization")]
+    DebtNotZero {},
+
+    #[error("terms are already set, change them through the terms delay")]
+    TermsAlreadySet {},
+
+    #[error("vesting must be longer than 36 hours")]
+    VestingTooShort {},
+
+    #[error("vesting_term only applies to fixed term bonds, vesting_end to fixed expiry bonds")]
+    VestingKindMismatch {},
+
+    #[error("payout cannot be above 1 percent")]
+    MaxPayoutTooHigh {},
+
+    #[error("increment too large: {increment} above {max}")]
+    IncrementTooLarge { increment: Decimal, max: Decimal },
+
+    #[error("no pending terms change is due")]
+    NoPendingTermsDue {},
+
+    #[error("pending terms change {id} not found")]
+    PendingTermsNotFound { id: u64 },
+
+    #[error("cannot sweep principal that is also the payout token")]
+    SweepPayoutToken {},
+
+    #[error("nothing to sweep")]
+    NothingToSweep {},
+
+    #[error("fee recipient weight must be positive")]
+    InvalidFeeRecipientWeight {},
+
+    #[error("fee recipient weights overflow")]
+    FeeRecipientWeightOverflow {},
+
+    #[error("referrer share {referrer_share} above 100%")]
+    InvalidReferrerShare { referrer_share: Decimal },
+
+    #[error("cannot refer your own deposit")]
+    SelfReferral {},
+
+    #[error("invalid denom received")]
+    InvalidDenom {},
+
+    #[error("not support cw20 token")]
+    NotSupportCw20 {},
+
+    #[error("not support native token")]
+    NotSupportNative {},
+
+    #[error("deposits must go through deposit_from to measure the received balance")]
+    ReceivedBalanceNotMeasured {},
+
+    #[error("invalid cw20 token")]
+    InvalidCw20Token {},
+
+    #[error("amount is zero")]
+    AmountIsZero {},
+
+    #[error("deadline exceeded: {deadline}")]
+    DeadlineExceeded { deadline: u64 },
+
+    #[error("depositor not approved")]
+    DepositorNotApproved {},
+
+    #[error("deposits are paused")]
+    DepositsPaused {},
+
+    #[error("bond has expired")]
+    BondExpired {},
+
+    #[error("slippage limit: price {price} more than max price {max_price}")]
+    MaxPriceExceeded { price: Decimal, max_price: Decimal },
+
+    #[error("slippage limit: payout {payout} less than min payout {min_payout}")]
+    MinPayoutNotMet {
+        payout: Uint128,
+        min_payout: Uint128,
+    },
+
+    #[error("price guard pair has no liquidity")]
+    PriceGuardNoLiquidity {},
+
+    #[error("bond price {bond_price} above market price {market_price}")]
+    BondPriceAboveMarket {
+        bond_price: Decimal,
+        market_price: Decimal,
+    },
+
+    #[error("discount below minimum: bond price {bond_price} above {max_price}")]
+    DiscountBelowMinimum {
+        bond_price: Decimal,
+        max_price: Decimal,
+    },
+
+    #[error("discount above maximum: bond price {bond_price} below {min_price}")]
+    DiscountAboveMaximum {
+        bond_price: Decimal,
+        min_price: Decimal,
+    },
+
+    #[error("bond too small: payout {payout} below {min}")]
+    BondTooSmall { payout: Uint128, min: Uint128 },
+
+    #[error("bond too large: payout {payout} above {max}")]
+    BondTooLarge { payout: Uint128, max: Uint128 },
+
+    #[error("max capacity reached: debt {debt} above {max_debt}")]
+    MaxCapacityReached { debt: Uint128, max_debt: Uint128 },
+
+    #[error("nothing to redeem")]
+    NothingToRedeem {},
+
+    #[error("duplicate note id: {note_id}")]
+    DuplicateNoteId { note_id: u64 },
+}
+
+impl From<DepositFailure> for ContractError {
+    fn from(failure: DepositFailure) -> Self {
+        match failure {
+            DepositFailure::DepositorNotApproved {} => ContractError::DepositorNotApproved {},
+            DepositFailure::AmountIsZero {} => ContractError::AmountIsZero {},
+            DepositFailure::DepositsPaused {} => ContractError::DepositsPaused {},
+            DepositFailure::BondExpired {} => ContractError::BondExpired {},
+            DepositFailure::MaxPriceExceeded { price, max_price } => {
+                ContractError::MaxPriceExceeded { price, max_price }
+            }
+            DepositFailure::InvalidPriceGuardPair {} => ContractError::InvalidPriceGuardPair {},
+            DepositFailure::PriceGuardNoLiquidity {} => ContractError::PriceGuardNoLiquidity {},
+            DepositFailure::BondPriceAboveMarket {
+                bond_price,
+                market_price,
+            } => ContractError::BondPriceAboveMarket {
+                bond_price,
+                market_price,
+            },
+            DepositFailure::DiscountBelowMinimum {
+                bond_price,
+                max_price,
+            } => ContractError::DiscountBelowMinimum {
+                bond_price,
+                max_price,
+            },
+            DepositFailure::DiscountAboveMaximum {
+                bond_price,
+                min_price,
+            } => ContractError::DiscountAboveMaximum {
+                bond_price,
+                min_price,
+            },
+            DepositFailure::BondTooSmall { payout, min } => {
+                ContractError::BondTooSmall { payout, min }
+            }
+            DepositFailure::BondTooLarge { payout, max } => {
+                ContractError::BondTooLarge { payout, max }
+            }
+            DepositFailure::MaxCapacityReached { debt, max_debt } => {
+                ContractError::MaxCapacityReached { debt, max_debt }
+            }
+        }
+    }
+}
@@ -1,49 +1,131 @@
 use cosmwasm_std::{
-    attr, to_binary, Addr, Attribute, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128, WasmMsg,
+    attr, to_binary, Addr, Attribute, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, ReplyOn, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
+use cw20::Cw20ExecuteMsg;
 use olympus_pro::{
-    custom_bond::{Adjustment, Terms},
+    custom_bond::{
+        Adjustment, BondInfo, Cw20HookMsg, FeeRecipient, FeeTier, PriceGuard, State, Terms,
+        TermsChange, VestingKind,
+    },
     custom_treasury::ExecuteMsg as CustomTreasuryExecuteMsg,
-    querier::query_token_supply,
-    utils::get_value_of_token,
+    note_nft::{ExecuteMsg as NoteNftExecuteMsg, MintMsg},
+    ownership::{accept, cancel, propose, KEY_POLICY_PROPOSAL},
+    utils::validate_fee_tiers,
 };
 use terraswap::asset::{Asset, AssetInfo};
 
 use crate::{
+    error::ContractError,
+    query::{query_note_owner, query_owned_note_ids},
     state::{
-        read_bond_info, read_config, read_state, remove_bond_info, store_bond_info, store_config,
-        store_state,
+        read_all_pending_terms, read_bond_info, read_bond_infos, read_config, read_note_depositor,
+        read_pending_terms, read_referrer_fees, read_state, read_temp_deposit, remove_bond_info,
+        remove_pending_terms, remove_temp_deposit, store_bond_info, store_config,
+        store_depositor_approval, store_new_bond_info, store_new_pending_terms, store_open_deposit,
+        store_referrer_fees, store_state, store_temp_deposit, Config, PendingTerms, TempDeposit,
+        KEY_OLYMPUS_DAO_PROPOSAL,
     },
     utils::{
-        adjust, decay_debt, decimal_multiplication_in_256, get_current_debt, get_debt_ratio,
-        get_max_payout, get_payout_for, get_pending_payout, get_true_bond_price,
+        adjust, decimal_multiplication_in_256, fee_recipients_to_raw, get_current_debt,
+        get_debt_ratio, get_pending_payout, get_vesting, into_msg_after_tax, is_depositor_approved,
+        price_guard_to_raw, quote_deposit, quote_partial_deposit, split_fee, DepositQuote,
     },
 };
 
-pub fn update_policy(deps: DepsMut, policy: String) -> StdResult<Response> {
+pub fn propose_new_policy(
+    deps: DepsMut,
+    env: Env,
+    policy: String,
+    expires: Option<u64>,
+) -> Result<Response, ContractError> {
+    propose(deps, &env, KEY_POLICY_PROPOSAL, &policy, expires)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_policy"),
+        attr("policy", policy),
+    ]))
+}
+
+pub fn accept_policy(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let policy = accept(deps.branch(), &env, KEY_POLICY_PROPOSAL, &info.sender)?;
+
     let mut config = read_config(deps.storage)?;
+    config.policy = policy;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_policy"),
+        attr("policy", info.sender),
+    ]))
+}
+
+pub fn cancel_proposal(deps: DepsMut) -> Result<Response, ContractError> {
+    cancel(deps.storage, KEY_POLICY_PROPOSAL)?;
+
+    Ok(Response::new().add_attribute("action", "cancel_proposal"))
+}
+
+pub fn propose_new_olympus_dao(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    olympus_dao: String,
+    expires: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_olympus_dao(deps.as_ref(), &info)?;
 
-    config.policy = deps.api.addr_canonicalize(&policy)?;
+    propose(deps, &env, KEY_OLYMPUS_DAO_PROPOSAL, &olympus_dao, expires)?;
 
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_olympus_dao"),
+        attr("olympus_dao", olympus_dao),
+    ]))
+}
+
+pub fn accept_olympus_dao(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let olympus_dao = accept(deps.branch(), &env, KEY_OLYMPUS_DAO_PROPOSAL, &info.sender)?;
+
+    let mut config = read_config(deps.storage)?;
+    config.olympus_dao = olympus_dao;
     store_config(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![
-        attr("action", "update_policy"),
-        attr("policy", policy),
+        attr("action", "accept_olympus_dao"),
+        attr("olympus_dao", info.sender),
     ]))
 }
 
+pub fn cancel_olympus_dao_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_olympus_dao(deps.as_ref(), &info)?;
+
+    cancel(deps.storage, KEY_OLYMPUS_DAO_PROPOSAL)?;
+
+    Ok(Response::new().add_attribute("action", "cancel_olympus_dao_proposal"))
+}
+
 pub fn update_olympus_treasury(
     deps: DepsMut,
     info: MessageInfo,
     olympus_treasury: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config = read_config(deps.storage)?;
 
-    if config.olympus_dao != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+    let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if config.olympus_dao != sender && config.fee_recipient_admin != Some(sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
     config.olympus_treasury = deps.api.addr_canonicalize(&olympus_treasury)?;
@@ -56,105 +138,325 @@ pub fn update_olympus_treasury(
     ]))
 }
 
+pub fn update_fee_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_tiers: Vec<FeeTier>,
+) -> Result<Response, ContractError> {
+    assert_olympus_dao(deps.as_ref(), &info)?;
+
+    validate_fee_tiers(&fee_tiers)?;
+
+    let mut config = read_config(deps.storage)?;
+
+    config.fee_tiers = fee_tiers;
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_fee_tiers")]))
+}
+
+pub fn update_fee_recipients(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_recipients: Vec<FeeRecipient>,
+    referrer_share: Decimal,
+) -> Result<Response, ContractError> {
+    assert_olympus_dao(deps.as_ref(), &info)?;
+
+    if referrer_share > Decimal::one() {
+        return Err(ContractError::InvalidReferrerShare { referrer_share });
+    }
+
+    let mut config = read_config(deps.storage)?;
+
+    config.fee_recipients = fee_recipients_to_raw(deps.api, fee_recipients)?;
+    config.referrer_share = referrer_share;
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_fee_recipients")]))
+}
+
+pub fn update_pauser(deps: DepsMut, pauser: String) -> Result<Response, ContractError> {
+    let mut config = read_config(deps.storage)?;
+
+    config.pauser = deps.api.addr_canonicalize(&pauser)?;
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_pauser"),
+        attr("pauser", pauser),
+    ]))
+}
+
+pub fn set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    if read_config(deps.storage)?.pauser != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut state = read_state(deps.storage)?;
+
+    state.paused = paused;
+
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![attr(
+        "action",
+        if paused { "pause" } else { "unpause" },
+    )]))
+}
+
+pub fn update_price_guard(
+    deps: DepsMut,
+    price_guard: Option<PriceGuard>,
+) -> Result<Response, ContractError> {
+    let mut config = read_config(deps.storage)?;
+
+    config.price_guard = price_guard_to_raw(
+        deps.as_ref(),
+        price_guard,
+        &config.payout_token.to_normal(deps.api)?,
+        &config.principal_token.to_normal(deps.api)?,
+        &config.bonding_calculator.to_normal(deps.api)?,
+    )?;
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_price_guard")]))
+}
+
 pub fn initialize_bond(
     deps: DepsMut,
     env: Env,
     terms: Terms,
     initial_debt: Uint128,
-) -> StdResult<Response> {
-    let mut state = read_state(deps.storage)?;
+) -> Result<Response, ContractError> {
+    // with a terms delay, set terms only change through the queue
+    if read_config(deps.storage)?.terms_delay > 0
+        && read_state(deps.storage)?.terms != Terms::default()
+    {
+        return Err(ContractError::TermsAlreadySet {});
+    }
 
-    let current_time = env.block.time.seconds();
+    store_initial_terms(deps.storage, env.block.time.seconds(), terms, initial_debt)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "initialize_bond")]))
+}
+
+/// Checks and stores the first terms of the bond, for `InitializeBond` and instantiate alike
+pub fn store_initial_terms(
+    storage: &mut dyn Storage,
+    current_time: u64,
+    terms: Terms,
+    initial_debt: Uint128,
+) -> Result<(), ContractError> {
+    let mut state = read_state(storage)?;
 
     if !get_current_debt(state.clone(), current_time).is_zero() {
-        return Err(StdError::generic_err("debt must be 0 for initialization"));
+        return Err(ContractError::DebtNotZero {});
     }
 
-    if terms.vesting_term < 129600 {
-        return Err(StdError::generic_err(
-            "vesting must be longer than 36 hours",
-        ));
+    if get_vesting(&terms, current_time) < 129600 {
+        return Err(ContractError::VestingTooShort {});
     }
     if terms.max_payout >= Decimal::percent(1) {
-        return Err(StdError::generic_err("payout cannot be above 1 percent"));
+        return Err(ContractError::MaxPayoutTooHigh {});
     }
 
     state.terms = terms;
-    state.last_decay = env.block.time.seconds();
+    state.last_decay = current_time;
     state.total_debt = initial_debt;
 
-    store_state(deps.storage, &state)?;
+    store_state(storage, &state)?;
 
-    Ok(Response::new().add_attributes(vec![attr("action", "initialize_bond")]))
+    Ok(())
 }
 
-pub fn set_bond_terms(
+/// Applies `change` right away when the terms delay is 0, otherwise queues it until the delay
+/// has passed. Invalid changes are rejected here rather than when applied.
+pub fn schedule_terms_change(
     deps: DepsMut,
-    vesting_term: Option<u64>,
-    max_payout: Option<Decimal>,
-    max_debt: Option<Uint128>,
-) -> StdResult<Response> {
+    env: Env,
+    change: TermsChange,
+) -> Result<Response, ContractError> {
+    let mut config = read_config(deps.storage)?;
     let mut state = read_state(deps.storage)?;
+    let terms_delay = config.terms_delay;
 
-    if let Some(vesting_term) = vesting_term {
-        if vesting_term < 129600 {
-            return Err(StdError::generic_err(
-                "vesting must be longer than 36 hours",
-            ));
-        }
-        state.terms.vesting_term = vesting_term;
+    apply_terms_change(&mut config, &mut state, &change, env.block.time.seconds())?;
+
+    let action = terms_change_action(&change);
+    if terms_delay == 0 {
+        store_config(deps.storage, &config)?;
+        store_state(deps.storage, &state)?;
+
+        return Ok(Response::new().add_attributes(vec![attr("action", action)]));
     }
 
-    if let Some(max_payout) = max_payout {
-        if max_payout >= Decimal::percent(1) {
-            return Err(StdError::generic_err("payout cannot be above 1 percent"));
-        }
-        state.terms.max_payout = max_payout;
+    let activation = env.block.time.seconds() + terms_delay;
+    let id = store_new_pending_terms(deps.storage, &PendingTerms { change, activation })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", action),
+        attr("pending_terms_id", id.to_string()),
+        attr("activation", activation.to_string()),
+    ]))
+}
+
+pub fn apply_pending_terms(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let current_time = env.block.time.seconds();
+    let due: Vec<(u64, PendingTerms)> = read_all_pending_terms(deps.storage)?
+        .into_iter()
+        .filter(|(_, pending_terms)| pending_terms.activation <= current_time)
+        .collect();
+
+    if due.is_empty() {
+        return Err(ContractError::NoPendingTermsDue {});
     }
 
-    if let Some(max_debt) = max_debt {
-        state.terms.max_debt = max_debt;
+    let mut config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
+
+    // a change that no longer validates is dropped so it cannot hold back the others
+    let mut applied: Vec<String> = vec![];
+    let mut dropped: Vec<Attribute> = vec![];
+    for (id, pending_terms) in due.into_iter() {
+        let mut new_config = config.clone();
+        let mut new_state = state.clone();
+        match apply_terms_change(
+            &mut new_config,
+            &mut new_state,
+            &pending_terms.change,
+            current_time,
+        ) {
+            Ok(()) => {
+                config = new_config;
+                state = new_state;
+                applied.push(id.to_string());
+            }
+            Err(err) => dropped.push(attr("dropped", format!("{}: {}", id, err))),
+        }
+        remove_pending_terms(deps.storage, id);
     }
 
+    store_config(deps.storage, &config)?;
     store_state(deps.storage, &state)?;
 
-    Ok(Response::new().add_attributes(vec![attr("action", "set_bond_terms")]))
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "apply_pending_terms"),
+            attr("applied", applied.join(",")),
+        ])
+        .add_attributes(dropped))
 }
 
-pub fn set_adjustment(
-    deps: DepsMut,
-    env: Env,
-    addition: bool,
-    increment: Decimal,
-    target: Decimal,
-    buffer: u64,
-) -> StdResult<Response> {
-    let mut state = read_state(deps.storage)?;
-
-    if increment
-        > decimal_multiplication_in_256(state.terms.control_variable, Decimal::percent(3u64))
-    {
-        return Err(StdError::generic_err("increment too large"));
+pub fn cancel_pending_terms(deps: DepsMut, id: u64) -> Result<Response, ContractError> {
+    if read_pending_terms(deps.storage, id)?.is_none() {
+        return Err(ContractError::PendingTermsNotFound { id });
     }
 
-    state.adjustment = Adjustment {
-        addition,
-        rate: increment,
-        target,
-        buffer,
-        last_time: env.block.time.seconds(),
-    };
+    remove_pending_terms(deps.storage, id);
 
-    store_state(deps.storage, &state)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_pending_terms"),
+        attr("pending_terms_id", id.to_string()),
+    ]))
+}
+
+fn apply_terms_change(
+    config: &mut Config,
+    state: &mut State,
+    change: &TermsChange,
+    current_time: u64,
+) -> Result<(), ContractError> {
+    match change.clone() {
+        TermsChange::BondTerms {
+            vesting_term,
+            vesting_end,
+            max_payout,
+            max_debt,
+        } => {
+            if let Some(vesting_term) = vesting_term {
+                if state.terms.vesting_kind != VestingKind::FixedTerm {
+                    return Err(ContractError::VestingKindMismatch {});
+                }
+                if vesting_term < 129600 {
+                    return Err(ContractError::VestingTooShort {});
+                }
+                state.terms.vesting_term = vesting_term;
+            }
+
+            if let Some(vesting_end) = vesting_end {
+                if state.terms.vesting_kind != VestingKind::FixedExpiry {
+                    return Err(ContractError::VestingKindMismatch {});
+                }
+                if vesting_end.saturating_sub(current_time) < 129600 {
+                    return Err(ContractError::VestingTooShort {});
+                }
+                state.terms.vesting_end = vesting_end;
+            }
+
+            if let Some(max_payout) = max_payout {
+                if max_payout >= Decimal::percent(1) {
+                    return Err(ContractError::MaxPayoutTooHigh {});
+                }
+                state.terms.max_payout = max_payout;
+            }
 
-    Ok(Response::new().add_attributes(vec![attr("action", "set_adjustment")]))
+            if let Some(max_debt) = max_debt {
+                state.terms.max_debt = max_debt;
+            }
+        }
+        TermsChange::Adjustment {
+            addition,
+            increment,
+            target,
+            buffer,
+        } => {
+            let max_increment =
+                decimal_multiplication_in_256(state.terms.control_variable, Decimal::percent(3u64));
+            if increment > max_increment {
+                return Err(ContractError::IncrementTooLarge {
+                    increment,
+                    max: max_increment,
+                });
+            }
+
+            state.adjustment = Adjustment {
+                addition,
+                rate: increment,
+                target,
+                buffer,
+                last_time: current_time,
+            };
+        }
+        TermsChange::TermsDelay { terms_delay } => {
+            config.terms_delay = terms_delay;
+        }
+    }
+
+    Ok(())
+}
+
+fn terms_change_action(change: &TermsChange) -> &'static str {
+    match change {
+        TermsChange::BondTerms { .. } => "set_bond_terms",
+        TermsChange::Adjustment { .. } => "set_adjustment",
+        TermsChange::TermsDelay { .. } => "set_terms_delay",
+    }
 }
 
-pub fn pay_subsidy(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+pub fn pay_subsidy(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
     if read_config(deps.storage)?.subsidy_router
         != deps.api.addr_canonicalize(info.sender.as_str())?
     {
-        return Err(StdError::generic_err("only subsidy controller"));
+        return Err(ContractError::OnlySubsidyController {});
     }
 
     let mut state = read_state(deps.storage)?;
@@ -166,88 +468,174 @@ pub fn pay_subsidy(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
     Ok(Response::new().add_attributes(vec![attr("action", "pay_subsidy")]))
 }
 
+pub fn sweep_principal(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.principal_token == config.payout_token {
+        return Err(ContractError::SweepPayoutToken {});
+    }
+
+    let principal_token = config.principal_token.to_normal(deps.api)?;
+    let amount = principal_token.query_pool(&deps.querier, deps.api, env.contract.address)?;
+
+    if amount.is_zero() {
+        return Err(ContractError::NothingToSweep {});
+    }
+
+    let principal_recipient = deps.api.addr_humanize(&config.principal_recipient)?;
+    let asset = Asset {
+        info: principal_token,
+        amount,
+    };
+
+    Ok(Response::new()
+        .add_message(asset.into_msg(&deps.querier, principal_recipient.clone())?)
+        .add_attributes(vec![
+            attr("action", "sweep_principal"),
+            attr("amount", amount.to_string()),
+            attr("recipient", principal_recipient.to_string()),
+        ]))
+}
+
+pub fn approve_depositor(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: Option<String>,
+) -> Result<Response, ContractError> {
+    set_depositor_approval(deps, info, operator, true)
+}
+
+pub fn revoke_depositor(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: Option<String>,
+) -> Result<Response, ContractError> {
+    set_depositor_approval(deps, info, operator, false)
+}
+
+fn set_depositor_approval(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: Option<String>,
+    approved: bool,
+) -> Result<Response, ContractError> {
+    let user = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    if let Some(operator) = operator.clone() {
+        store_depositor_approval(
+            deps.storage,
+            &user,
+            &deps.api.addr_canonicalize(&operator)?,
+            &approved,
+        )?;
+    } else {
+        store_open_deposit(deps.storage, &user, &approved)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr(
+            "action",
+            if approved {
+                "approve_depositor"
+            } else {
+                "revoke_depositor"
+            },
+        ),
+        attr("user", info.sender.to_string()),
+        attr("operator", operator.unwrap_or_else(|| String::from("*"))),
+    ]))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn deposit(
     deps: DepsMut,
     env: Env,
+    sender: String,
     amount: Uint128,
     max_price: Decimal,
     depositor: String,
-) -> StdResult<Response> {
+    allow_partial: bool,
+    min_payout: Option<Uint128>,
+    deadline: Option<u64>,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
     if amount.is_zero() {
-        return Err(StdError::generic_err("amount is zero"));
+        return Err(ContractError::AmountIsZero {});
     }
 
-    let config = read_config(deps.storage)?;
-    let mut state = read_state(deps.storage)?;
-
     let current_time = env.block.time.seconds();
+    if let Some(deadline) = deadline {
+        if current_time > deadline {
+            return Err(ContractError::DeadlineExceeded { deadline });
+        }
+    }
 
-    decay_debt(&mut state, current_time);
-
-    let payout_total_supply = query_token_supply(
-        &deps.querier,
-        deps.api.addr_humanize(&config.payout_token)?.to_string(),
-    )?;
-
-    let native_price = get_true_bond_price(
-        config.clone(),
-        state.clone(),
-        payout_total_supply,
-        current_time,
-    );
+    if !is_depositor_approved(
+        deps.storage,
+        &deps.api.addr_canonicalize(&depositor)?,
+        &deps.api.addr_canonicalize(&sender)?,
+    ) {
+        return Err(ContractError::DepositorNotApproved {});
+    }
 
-    if max_price < native_price {
-        return Err(StdError::generic_err("slippage limit: more than max price"));
+    let referrer = referrer
+        .map(|referrer| deps.api.addr_validate(&referrer))
+        .transpose()?;
+    if let Some(referrer) = referrer.as_ref() {
+        if referrer.as_str() == sender || referrer.as_str() == depositor {
+            return Err(ContractError::SelfReferral {});
+        }
     }
 
-    let value = get_value_of_token(
-        Asset {
-            info: config.principal_token.to_normal(deps.api)?,
-            amount,
-        },
-        config.payout_decimals,
-        config.principal_decimals,
-    );
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
 
-    let (payout, fee) = if config.fee_in_payout {
-        get_payout_for(
-            deps.as_ref(),
-            config.clone(),
-            state.clone(),
-            value,
+    let (
+        filled,
+        DepositQuote {
+            payout,
+            fee,
+            true_price,
+            vesting,
             payout_total_supply,
-            current_time,
-        )?
-    } else {
-        get_payout_for(
+            failures,
+            ..
+        },
+    ) = if allow_partial {
+        quote_partial_deposit(
             deps.as_ref(),
-            config.clone(),
-            state.clone(),
+            &config,
+            &mut state,
             amount,
-            payout_total_supply,
+            Some(max_price),
             current_time,
         )?
+    } else {
+        (
+            amount,
+            quote_deposit(
+                deps.as_ref(),
+                &config,
+                &mut state,
+                amount,
+                Some(max_price),
+                current_time,
+            )?,
+        )
     };
 
-    if payout
-        < Uint128::from(
-            10u128
-                .checked_pow((config.payout_decimals - 2) as u32)
-                .unwrap(),
-        )
-    {
-        return Err(StdError::generic_err("bond too small"));
-    } else if payout > get_max_payout(state.clone(), payout_total_supply)? {
-        return Err(StdError::generic_err("bond too large"));
+    if let Some(failure) = failures.into_iter().next() {
+        return Err(failure.into());
     }
-
-    state.total_debt += value;
-    if state.total_debt > state.terms.max_debt {
-        return Err(StdError::generic_err("max capacity reached"));
+    if let Some(min_payout) = min_payout {
+        if payout < min_payout {
+            return Err(ContractError::MinPayoutNotMet { payout, min_payout });
+        }
     }
 
     let mut payout_from_treasury = payout;
-    let mut amount_without_fee = amount;
+    let mut amount_without_fee = filled;
+    let refunded = amount.checked_sub(filled)?;
 
     if config.fee_in_payout {
         payout_from_treasury += fee;
@@ -255,19 +643,18 @@ pub fn deposit(
         amount_without_fee = amount_without_fee.checked_sub(fee)?;
     }
 
-    let mut bond_info =
-        read_bond_info(deps.storage, deps.api.addr_canonicalize(&depositor)?).unwrap_or_default();
-    bond_info.payout += payout;
-    bond_info.vesting = state.terms.vesting_term;
-    bond_info.last_time = current_time;
-    bond_info.true_price_paid = native_price;
-    store_bond_info(
+    let bond_info = BondInfo {
+        payout,
+        vesting,
+        last_time: current_time,
+        true_price_paid: true_price,
+    };
+    let note_id = store_new_bond_info(
         deps.storage,
         &bond_info,
         deps.api.addr_canonicalize(&depositor)?,
     )?;
 
-    state.total_principal_bonded += amount_without_fee;
     state.total_payout_given += payout;
     state.payout_since_last_subsidy += payout;
 
@@ -280,21 +667,80 @@ pub fn deposit(
         })?,
     }));
 
+    let mut referrer_fee = Uint128::zero();
     if !fee.is_zero() {
+        let fee_asset_info = if config.fee_in_payout {
+            config.payout_token.to_normal(deps.api)?
+        } else {
+            config.principal_token.to_normal(deps.api)?
+        };
+
+        let (referrer_share, recipients) = split_fee(deps.api, &config, fee, referrer.is_some())?;
+        if let Some(referrer) = referrer.clone() {
+            if !referrer_share.is_zero() {
+                let asset = Asset {
+                    info: fee_asset_info.clone(),
+                    amount: referrer_share,
+                };
+                let (msg, received) = into_msg_after_tax(&deps.querier, asset, referrer)?;
+                messages.push(msg);
+                referrer_fee = received;
+            }
+        }
+
+        for (recipient, amount) in recipients {
+            if amount.is_zero() {
+                continue;
+            }
+            let asset = Asset {
+                info: fee_asset_info.clone(),
+                amount,
+            };
+            messages.push(asset.into_msg(&deps.querier, recipient)?);
+        }
+    }
+
+    if let Some(referrer) = referrer.as_ref() {
+        if !referrer_fee.is_zero() {
+            let referrer_raw = deps.api.addr_canonicalize(referrer.as_str())?;
+            let total_fees = read_referrer_fees(deps.storage, &referrer_raw)? + referrer_fee;
+            store_referrer_fees(deps.storage, &referrer_raw, &total_fees)?;
+        }
+    }
+
+    if let Some(note_nft) = config.note_nft.clone() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&note_nft)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&NoteNftExecuteMsg::Mint(MintMsg {
+                token_id: note_id.to_string(),
+                owner: depositor,
+                token_uri: None,
+                extension: bond_info,
+            }))?,
+        }));
+    }
+
+    if !amount_without_fee.is_zero() {
         let asset = Asset {
-            info: if config.fee_in_payout {
-                AssetInfo::Token {
-                    contract_addr: deps.api.addr_humanize(&config.payout_token)?.to_string(),
-                }
-            } else {
-                config.principal_token.to_normal(deps.api)?
-            },
-            amount: fee,
+            info: config.principal_token.to_normal(deps.api)?,
+            amount: amount_without_fee,
         };
-        messages.push(asset.into_msg(
+        let (msg, received) = into_msg_after_tax(
             &deps.querier,
-            deps.api.addr_humanize(&config.olympus_treasury)?,
-        )?)
+            asset,
+            deps.api.addr_humanize(&config.principal_recipient)?,
+        )?;
+        messages.push(msg);
+        state.total_principal_bonded += received;
+    }
+
+    if !refunded.is_zero() {
+        let asset = Asset {
+            info: config.principal_token.to_normal(deps.api)?,
+            amount: refunded,
+        };
+        messages.push(asset.into_msg(&deps.querier, deps.api.addr_validate(&sender)?)?)
     }
 
     let mut bond_price = decimal_multiplication_in_256(
@@ -310,17 +756,25 @@ pub fn deposit(
     let mut attrs: Vec<Attribute> = vec![
         attr("action", "deposit"),
         attr("amount", amount.to_string()),
+    ];
+    if allow_partial {
+        attrs.push(attr("filled", filled.to_string()));
+        attrs.push(attr("refunded", refunded.to_string()));
+    }
+    attrs.extend(vec![
         attr("payout", payout.to_string()),
-        attr(
-            "expires",
-            (current_time + state.terms.vesting_term).to_string(),
-        ),
+        attr("note_id", note_id.to_string()),
+        attr("expires", (current_time + vesting).to_string()),
         attr("bond_price", bond_price.to_string()),
         attr(
             "debt_ratio",
             get_debt_ratio(state.clone(), payout_total_supply, current_time).to_string(),
         ),
-    ];
+    ]);
+    if let Some(referrer) = referrer {
+        attrs.push(attr("referrer", referrer.to_string()));
+        attrs.push(attr("referrer_fee", referrer_fee.to_string()));
+    }
 
     let (adjusted, initial_value) = adjust(&mut state, current_time)?;
     if adjusted {
@@ -338,38 +792,208 @@ pub fn deposit(
     Ok(Response::new().add_attributes(attrs).add_messages(messages))
 }
 
-pub fn redeem(deps: DepsMut, env: Env, user: String) -> StdResult<Response> {
-    let mut bond_info = read_bond_info(deps.storage, deps.api.addr_canonicalize(&user)?)?;
+/// Pulls `amount` of the cw20 principal from `sender`, `deposit_received` bonds what arrived
+pub fn deposit_from(
+    deps: DepsMut,
+    env: Env,
+    sender: String,
+    amount: Uint128,
+    msg: Cw20HookMsg,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::AmountIsZero {});
+    }
 
-    let time_since_last = env.block.time.seconds() - bond_info.last_time;
+    let config = read_config(deps.storage)?;
+    let principal_token = config.principal_token.to_normal(deps.api)?;
+    let contract_addr = match principal_token.clone() {
+        AssetInfo::Token { contract_addr } => contract_addr,
+        AssetInfo::NativeToken { .. } => return Err(ContractError::NotSupportNative {}),
+    };
 
-    let payout = get_pending_payout(bond_info.clone(), time_since_last);
+    let balance_before =
+        principal_token.query_pool(&deps.querier, deps.api, env.contract.address.clone())?;
+    store_temp_deposit(
+        deps.storage,
+        &TempDeposit {
+            sender: sender.clone(),
+            balance_before,
+            msg,
+        },
+    )?;
 
-    if payout.is_zero() {
-        return Err(StdError::generic_err("nothing to redeem"));
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "deposit_from"),
+            attr("amount", amount.to_string()),
+        ])
+        .add_submessage(SubMsg {
+            id: 2,
+            gas_limit: None,
+            msg: WasmMsg::Execute {
+                contract_addr,
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: sender,
+                    recipient: env.contract.address.to_string(),
+                    amount,
+                })?,
+            }
+            .into(),
+            reply_on: ReplyOn::Success,
+        }))
+}
+
+/// Bonds the principal balance gained since `deposit_from`
+pub fn deposit_received(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let temp_deposit = read_temp_deposit(deps.storage)?;
+    remove_temp_deposit(deps.storage);
+
+    let config = read_config(deps.storage)?;
+    let balance = config.principal_token.to_normal(deps.api)?.query_pool(
+        &deps.querier,
+        deps.api,
+        env.contract.address.clone(),
+    )?;
+    let received = balance.checked_sub(temp_deposit.balance_before)?;
+
+    match temp_deposit.msg {
+        Cw20HookMsg::Deposit {
+            max_price,
+            depositor,
+            allow_partial,
+            min_payout,
+            deadline,
+            referrer,
+        } => deposit(
+            deps,
+            env,
+            temp_deposit.sender,
+            received,
+            max_price,
+            depositor,
+            allow_partial,
+            min_payout,
+            deadline,
+            referrer,
+        ),
     }
+}
+
+pub fn redeem(
+    deps: DepsMut,
+    env: Env,
+    user: String,
+    note_ids: Option<Vec<u64>>,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+    let current_time = env.block.time.seconds();
 
-    bond_info.payout = bond_info.payout.checked_sub(payout)?;
-    if bond_info.payout.is_zero() {
-        remove_bond_info(deps.storage, deps.api.addr_canonicalize(&user)?);
+    // every note is read before any is paid, so a repeated id would be paid again
+    if let Some(note_ids) = &note_ids {
+        let mut sorted_note_ids = note_ids.clone();
+        sorted_note_ids.sort_unstable();
+        if let Some(pair) = sorted_note_ids.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(ContractError::DuplicateNoteId { note_id: pair[0] });
+        }
+    }
+
+    let note_nft = config
+        .note_nft
+        .clone()
+        .map(|note_nft| deps.api.addr_humanize(&note_nft))
+        .transpose()?;
+    let bond_infos = if let Some(note_nft) = note_nft.clone() {
+        let note_nft = note_nft.to_string();
+        let note_ids = if let Some(note_ids) = note_ids {
+            for note_id in note_ids.iter() {
+                if query_note_owner(&deps.querier, note_nft.clone(), *note_id)? != user {
+                    return Err(ContractError::Unauthorized {});
+                }
+            }
+            note_ids
+        } else {
+            query_owned_note_ids(&deps.querier, note_nft, user.clone())?
+        };
+
+        note_ids
+            .into_iter()
+            .map(|note_id| {
+                let depositor = read_note_depositor(deps.storage, note_id)?;
+                let bond_info = read_bond_info(deps.storage, depositor.clone(), note_id)?;
+                Ok((depositor, note_id, bond_info))
+            })
+            .collect::<StdResult<Vec<(CanonicalAddr, u64, BondInfo)>>>()?
     } else {
-        bond_info.vesting = bond_info.vesting - time_since_last;
-        bond_info.last_time = env.block.time.seconds();
-        store_bond_info(deps.storage, &bond_info, deps.api.addr_canonicalize(&user)?)?;
+        let user_raw = deps.api.addr_canonicalize(&user)?;
+        if let Some(note_ids) = note_ids {
+            note_ids
+                .into_iter()
+                .map(|note_id| {
+                    let bond_info = read_bond_info(deps.storage, user_raw.clone(), note_id)?;
+                    Ok((user_raw.clone(), note_id, bond_info))
+                })
+                .collect::<StdResult<Vec<(CanonicalAddr, u64, BondInfo)>>>()?
+        } else {
+            read_bond_infos(deps.storage, user_raw.clone())?
+                .into_iter()
+                .map(|(note_id, bond_info)| (user_raw.clone(), note_id, bond_info))
+                .collect()
+        }
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut total_payout = Uint128::zero();
+    for (depositor, note_id, mut bond_info) in bond_infos {
+        let time_since_last = current_time - bond_info.last_time;
+
+        let payout = get_pending_payout(bond_info.clone(), time_since_last);
+        if payout.is_zero() {
+            continue;
+        }
+
+        bond_info.payout = bond_info.payout.checked_sub(payout)?;
+        if bond_info.payout.is_zero() {
+            remove_bond_info(deps.storage, depositor, note_id)?;
+            if let Some(note_nft) = note_nft.as_ref() {
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: note_nft.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&NoteNftExecuteMsg::Burn {
+                        token_id: note_id.to_string(),
+                    })?,
+                }));
+            }
+        } else {
+            bond_info.vesting -= time_since_last;
+            bond_info.last_time = current_time;
+            store_bond_info(deps.storage, &bond_info, depositor, note_id)?;
+        }
+
+        total_payout += payout;
+    }
+
+    if total_payout.is_zero() {
+        return Err(ContractError::NothingToRedeem {});
     }
 
-    let config = read_config(deps.storage)?;
     let asset = Asset {
-        info: AssetInfo::Token {
-            contract_addr: deps.api.addr_humanize(&config.payout_token)?.to_string(),
-        },
-        amount: payout,
+        info: config.payout_token.to_normal(deps.api)?,
+        amount: total_payout,
     };
 
-    Ok(Response::new()
-        .add_message(asset.into_msg(&deps.querier, Addr::unchecked(user))?)
-        .add_attributes(vec![
-            attr("action", "redeem"),
-            attr("amount", payout.to_string()),
-        ]))
+    messages.push(asset.into_msg(&deps.querier, Addr::unchecked(user))?);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "redeem"),
+        attr("amount", total_payout.to_string()),
+    ]))
+}
+
+fn assert_olympus_dao(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if read_config(deps.storage)?.olympus_dao != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(())
 }
@@ -1,4 +1,5 @@
 pub mod contract;
+pub mod error;
 mod execute;
 mod query;
 mod state;
@@ -1,20 +1,30 @@
 use cosmwasm_std::{
-    to_binary, Decimal, Deps, Env, QuerierWrapper, QueryRequest, StdResult, Uint128, WasmQuery,
+    to_binary, CanonicalAddr, Decimal, Deps, Env, QuerierWrapper, QueryRequest, StdError,
+    StdResult, Uint128, WasmQuery,
 };
 
+use cw721::{Cw721QueryMsg, OwnerOfResponse, TokensResponse};
 use olympus_pro::{
-    custom_bond::{BondInfoResponse, ConfigResponse, State},
+    custom_bond::{
+        BondInfo, BondInfoResponse, BondSummaryResponse, ConfigResponse, CurrentOlympusFeeResponse,
+        DepositFailure, FeeRecipient, HolderBondInfosResponse, PendingTermsResponse, PriceGuard,
+        ReferrerResponse, SimulateDepositResponse, State,
+    },
     custom_treasury::{
         ConfigResponse as CustomTreasuryConfigResponse, QueryMsg as CustomTreasuryQueryMsg,
     },
-    querier::query_token_supply,
+    querier::query_supply,
 };
 
 use crate::{
-    state::{read_bond_info, read_config, read_state},
+    error::ContractError,
+    state::{
+        read_all_pending_terms, read_bond_holder_notes, read_bond_info, read_bond_infos,
+        read_bond_summary, read_config, read_note_depositor, read_referrer_fees, read_state,
+    },
     utils::{
-        get_bond_price, get_current_debt, get_current_olympus_fee, get_payout_for,
-        get_pending_payout,
+        get_bond_price, get_current_debt, get_current_fee_tier, get_current_olympus_fee,
+        get_debt_ratio, get_payout_for, get_pending_payout, is_depositor_approved, quote_deposit,
     },
 };
 
@@ -23,7 +33,7 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 
     let resp = ConfigResponse {
         custom_treasury: deps.api.addr_humanize(&config.custom_treasury)?.to_string(),
-        payout_token: deps.api.addr_humanize(&config.payout_token)?.to_string(),
+        payout_token: config.payout_token.to_normal(deps.api)?,
         principal_token: config.principal_token.to_normal(deps.api)?,
         olympus_treasury: deps
             .api
@@ -34,6 +44,46 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         olympus_dao: deps.api.addr_humanize(&config.olympus_dao)?.to_string(),
         fee_tiers: config.fee_tiers,
         fee_in_payout: config.fee_in_payout,
+        principal_recipient: deps
+            .api
+            .addr_humanize(&config.principal_recipient)?
+            .to_string(),
+        note_nft: config
+            .note_nft
+            .map(|note_nft| deps.api.addr_humanize(&note_nft))
+            .transpose()?
+            .map(|note_nft| note_nft.to_string()),
+        pauser: deps.api.addr_humanize(&config.pauser)?.to_string(),
+        price_guard: config
+            .price_guard
+            .map(|price_guard| -> StdResult<PriceGuard> {
+                Ok(PriceGuard {
+                    pair: deps.api.addr_humanize(&price_guard.pair)?.to_string(),
+                    min_discount: price_guard.min_discount,
+                    max_discount: price_guard.max_discount,
+                })
+            })
+            .transpose()?,
+        bonding_calculator: config.bonding_calculator.to_normal(deps.api)?,
+        terms_delay: config.terms_delay,
+        fee_recipients: config
+            .fee_recipients
+            .iter()
+            .map(|fee_recipient| {
+                Ok(FeeRecipient {
+                    address: deps.api.addr_humanize(&fee_recipient.address)?.to_string(),
+                    weight: fee_recipient.weight,
+                })
+            })
+            .collect::<StdResult<Vec<FeeRecipient>>>()?,
+        referrer_share: config.referrer_share,
+        measure_received_balance: config.measure_received_balance,
+        fee_recipient_admin: match config.fee_recipient_admin {
+            Some(fee_recipient_admin) => {
+                Some(deps.api.addr_humanize(&fee_recipient_admin)?.to_string())
+            }
+            None => None,
+        },
     };
 
     Ok(resp)
@@ -58,33 +108,181 @@ pub fn query_custom_treasury_config(
     Ok(res)
 }
 
-pub fn query_bond_info(deps: Deps, env: Env, user: String) -> StdResult<BondInfoResponse> {
-    let bond_info = read_bond_info(deps.storage, deps.api.addr_canonicalize(&user)?)?;
+/// Notes of `user`, which are the notes whose token `user` owns when notes are NFTs
+pub fn query_bond_info(deps: Deps, env: Env, user: String) -> StdResult<Vec<BondInfoResponse>> {
+    let config = read_config(deps.storage)?;
+    let note_nft = match config.note_nft {
+        Some(note_nft) => deps.api.addr_humanize(&note_nft)?.to_string(),
+        None => return query_holder_bond_infos(deps, &env, deps.api.addr_canonicalize(&user)?),
+    };
+
+    query_owned_note_ids(&deps.querier, note_nft, user)?
+        .into_iter()
+        .map(|note_id| {
+            let depositor = read_note_depositor(deps.storage, note_id)?;
+            let bond_info = read_bond_info(deps.storage, depositor, note_id)?;
+            Ok(bond_info_to_response(&env, note_id, bond_info))
+        })
+        .collect()
+}
+
+pub fn query_all_bond_infos(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    start_after_note_id: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<HolderBondInfosResponse>> {
+    let start_after = start_after
+        .map(|start_after| deps.api.addr_canonicalize(&start_after))
+        .transpose()?;
+
+    read_bond_holder_notes(deps.storage, start_after, start_after_note_id, limit)?
+        .into_iter()
+        .map(|(user, notes)| {
+            Ok(HolderBondInfosResponse {
+                user: deps.api.addr_humanize(&user)?.to_string(),
+                bond_infos: notes
+                    .into_iter()
+                    .map(|(note_id, bond_info)| bond_info_to_response(&env, note_id, bond_info))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+pub fn query_bond_summary(deps: Deps) -> StdResult<BondSummaryResponse> {
+    let summary = read_bond_summary(deps.storage)?;
 
+    Ok(BondSummaryResponse {
+        holder_count: summary.holder_count,
+        total_payout: summary.total_payout,
+    })
+}
+
+fn query_holder_bond_infos(
+    deps: Deps,
+    env: &Env,
+    user: CanonicalAddr,
+) -> StdResult<Vec<BondInfoResponse>> {
+    let bond_infos = read_bond_infos(deps.storage, user)?;
+
+    Ok(bond_infos
+        .into_iter()
+        .map(|(note_id, bond_info)| bond_info_to_response(env, note_id, bond_info))
+        .collect())
+}
+
+pub fn query_note(deps: Deps, env: Env, note_id: u64) -> StdResult<BondInfoResponse> {
+    let depositor = read_note_depositor(deps.storage, note_id)?;
+    let bond_info = read_bond_info(deps.storage, depositor, note_id)?;
+
+    Ok(bond_info_to_response(&env, note_id, bond_info))
+}
+
+fn bond_info_to_response(env: &Env, note_id: u64, bond_info: BondInfo) -> BondInfoResponse {
     let time_since_last = env.block.time.seconds() - bond_info.last_time;
 
     let pending_payout = get_pending_payout(bond_info.clone(), time_since_last);
 
-    Ok(BondInfoResponse {
+    BondInfoResponse {
+        note_id,
         info: bond_info,
         pending_payout,
+    }
+}
+
+pub fn query_note_owner(
+    querier: &QuerierWrapper,
+    note_nft: String,
+    note_id: u64,
+) -> StdResult<String> {
+    let res: OwnerOfResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: note_nft,
+        msg: to_binary(&Cw721QueryMsg::OwnerOf {
+            token_id: note_id.to_string(),
+            include_expired: None,
+        })?,
+    }))?;
+
+    Ok(res.owner)
+}
+
+pub fn query_owned_note_ids(
+    querier: &QuerierWrapper,
+    note_nft: String,
+    owner: String,
+) -> StdResult<Vec<u64>> {
+    let limit = 30u32;
+    let mut note_ids: Vec<u64> = vec![];
+    let mut start_after: Option<String> = None;
+
+    loop {
+        let res: TokensResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: note_nft.clone(),
+            msg: to_binary(&Cw721QueryMsg::Tokens {
+                owner: owner.clone(),
+                start_after,
+                limit: Some(limit),
+            })?,
+        }))?;
+
+        for token_id in res.tokens.iter() {
+            note_ids.push(
+                token_id
+                    .parse::<u64>()
+                    .map_err(|_| StdError::parse_err("u64", "invalid note token id"))?,
+            );
+        }
+
+        if res.tokens.len() < limit as usize {
+            return Ok(note_ids);
+        }
+        start_after = res.tokens.last().cloned();
+    }
+}
+
+pub fn query_depositor_approval(deps: Deps, user: String, operator: String) -> StdResult<bool> {
+    Ok(is_depositor_approved(
+        deps.storage,
+        &deps.api.addr_canonicalize(&user)?,
+        &deps.api.addr_canonicalize(&operator)?,
+    ))
+}
+
+pub fn query_pending_terms(deps: Deps) -> StdResult<Vec<PendingTermsResponse>> {
+    Ok(read_all_pending_terms(deps.storage)?
+        .into_iter()
+        .map(|(id, pending_terms)| PendingTermsResponse {
+            id,
+            change: pending_terms.change,
+            activation: pending_terms.activation,
+        })
+        .collect())
+}
+
+pub fn query_referrer(deps: Deps, referrer: String) -> StdResult<ReferrerResponse> {
+    Ok(ReferrerResponse {
+        total_fees: read_referrer_fees(deps.storage, &deps.api.addr_canonicalize(&referrer)?)?,
+        referrer,
     })
 }
 
-pub fn query_current_olympus_fee(deps: Deps) -> StdResult<Decimal> {
+pub fn query_current_olympus_fee(deps: Deps) -> StdResult<CurrentOlympusFeeResponse> {
     let config = read_config(deps.storage)?;
     let state = read_state(deps.storage)?;
-    Ok(get_current_olympus_fee(config, state))
+    Ok(CurrentOlympusFeeResponse {
+        tier: get_current_fee_tier(&config, &state).map(|tier| tier as u32),
+        fee_rate: get_current_olympus_fee(config, state),
+    })
 }
 
 pub fn query_bond_price(deps: Deps, env: Env) -> StdResult<Decimal> {
     let config = read_config(deps.storage)?;
     let state = read_state(deps.storage)?;
 
-    let payout_total_supply = query_token_supply(
-        &deps.querier,
-        deps.api.addr_humanize(&config.payout_token)?.to_string(),
-    )?;
+    let payout_total_supply =
+        query_supply(&deps.querier, &config.payout_token.to_normal(deps.api)?)?;
 
     Ok(get_bond_price(
         state,
@@ -93,23 +291,58 @@ pub fn query_bond_price(deps: Deps, env: Env) -> StdResult<Decimal> {
     ))
 }
 
-pub fn query_payout_for(deps: Deps, env: Env, value: Uint128) -> StdResult<(Uint128, Uint128)> {
+pub fn query_payout_for(
+    deps: Deps,
+    env: Env,
+    value: Uint128,
+) -> Result<(Uint128, Uint128), ContractError> {
     let config = read_config(deps.storage)?;
     let state = read_state(deps.storage)?;
 
-    let payout_total_supply = query_token_supply(
-        &deps.querier,
-        deps.api.addr_humanize(&config.payout_token)?.to_string(),
-    )?;
+    let payout_total_supply =
+        query_supply(&deps.querier, &config.payout_token.to_normal(deps.api)?)?;
 
-    Ok(get_payout_for(
+    get_payout_for(
         deps,
         config,
         state,
         value,
         payout_total_supply,
         env.block.time.seconds(),
-    )?)
+    )
+}
+
+/// Runs the checks of a deposit of `amount` principal for `depositor` without executing it
+pub fn query_simulate_deposit(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+    depositor: String,
+    sender: String,
+) -> Result<SimulateDepositResponse, ContractError> {
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
+    let current_time = env.block.time.seconds();
+
+    let mut quote = quote_deposit(deps, &config, &mut state, amount, None, current_time)?;
+    if !is_depositor_approved(
+        deps.storage,
+        &deps.api.addr_canonicalize(&depositor)?,
+        &deps.api.addr_canonicalize(&sender)?,
+    ) {
+        quote
+            .failures
+            .insert(0, DepositFailure::DepositorNotApproved {});
+    }
+
+    Ok(SimulateDepositResponse {
+        payout: quote.payout,
+        fee: quote.fee,
+        true_price: quote.true_price,
+        expires: current_time + quote.vesting,
+        debt_ratio: get_debt_ratio(state, quote.payout_total_supply, current_time),
+        failures: quote.failures,
+    })
 }
 
 pub fn query_current_debt(deps: Deps, env: Env) -> StdResult<Uint128> {
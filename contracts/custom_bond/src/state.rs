@@ -1,20 +1,40 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
-use cosmwasm_storage::{bucket, bucket_read, singleton, singleton_read, Bucket};
+use cosmwasm_std::{CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{
+    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, Singleton,
+};
 
-use olympus_pro::custom_bond::{BondInfo, FeeTier, State};
+use olympus_pro::{
+    bonding_calculator::BondingCalculatorRaw,
+    custom_bond::{BondInfo, Cw20HookMsg, FeeTier, State, TermsChange},
+};
 use terraswap::asset::AssetInfoRaw;
 
 const KEY_CONFIG: &[u8] = b"config";
 const KEY_STATE: &[u8] = b"state";
+const KEY_NOTE_COUNT: &[u8] = b"note_count";
 const PREFIX_KEY_BOND_INFO: &[u8] = b"prefix_bond_info";
+const PREFIX_KEY_BOND_HOLDER: &[u8] = b"bond_holder";
+const KEY_BOND_SUMMARY: &[u8] = b"bond_summary";
+const PREFIX_KEY_NOTE_DEPOSITOR: &[u8] = b"note_depositor";
+const PREFIX_KEY_DEPOSITOR_APPROVAL: &[u8] = b"depositor_approval";
+const PREFIX_KEY_OPEN_DEPOSIT: &[u8] = b"open_deposit";
+const KEY_PENDING_TERMS_COUNT: &[u8] = b"pending_terms_count";
+const PREFIX_KEY_PENDING_TERMS: &[u8] = b"pending_terms";
+const PREFIX_KEY_REFERRER_FEES: &[u8] = b"referrer_fees";
+const KEY_TEMP_DEPOSIT: &[u8] = b"temp_deposit";
+
+pub const KEY_OLYMPUS_DAO_PROPOSAL: &[u8] = b"olympus_dao_proposal";
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub custom_treasury: CanonicalAddr,
-    pub payout_token: CanonicalAddr,
+    pub payout_token: AssetInfoRaw,
     pub principal_token: AssetInfoRaw,
     pub olympus_treasury: CanonicalAddr,
     pub subsidy_router: CanonicalAddr,
@@ -24,6 +44,49 @@ pub struct Config {
     pub fee_in_payout: bool,
     pub payout_decimals: u8,
     pub principal_decimals: u8,
+    pub principal_recipient: CanonicalAddr,
+    pub note_nft: Option<CanonicalAddr>,
+    pub pauser: CanonicalAddr,
+    pub price_guard: Option<PriceGuardRaw>,
+    pub bonding_calculator: BondingCalculatorRaw,
+    pub terms_delay: u64,
+    pub fee_recipients: Vec<FeeRecipientRaw>,
+    pub referrer_share: Decimal,
+    pub measure_received_balance: bool,
+    pub fee_recipient_admin: Option<CanonicalAddr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipientRaw {
+    pub address: CanonicalAddr,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingTerms {
+    pub change: TermsChange,
+    pub activation: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BondSummary {
+    pub holder_count: u64,
+    pub total_payout: Uint128,
+}
+
+/// Deposit waiting for its principal `TransferFrom` to settle
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TempDeposit {
+    pub sender: String,
+    pub balance_before: Uint128,
+    pub msg: Cw20HookMsg,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceGuardRaw {
+    pub pair: CanonicalAddr,
+    pub min_discount: Decimal,
+    pub max_discount: Decimal,
 }
 
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
@@ -42,19 +105,275 @@ pub fn read_state(storage: &dyn Storage) -> StdResult<State> {
     Ok(singleton_read(storage, KEY_STATE).load()?)
 }
 
+pub fn store_new_bond_info(
+    storage: &mut dyn Storage,
+    bond_info: &BondInfo,
+    user: CanonicalAddr,
+) -> StdResult<u64> {
+    let note_id: u64 = singleton_read(storage, KEY_NOTE_COUNT)
+        .may_load()?
+        .unwrap_or_default();
+
+    store_bond_info(storage, bond_info, user.clone(), note_id)?;
+    bucket(storage, PREFIX_KEY_NOTE_DEPOSITOR).save(&note_id.to_be_bytes(), &user)?;
+
+    singleton(storage, KEY_NOTE_COUNT).save(&(note_id + 1))?;
+
+    Ok(note_id)
+}
+
+/// Saves the note and keeps the holder index and bond summary in sync
 pub fn store_bond_info(
     storage: &mut dyn Storage,
     bond_info: &BondInfo,
     user: CanonicalAddr,
+    note_id: u64,
+) -> StdResult<()> {
+    let mut bond_bucket: Bucket<BondInfo> =
+        Bucket::multilevel(storage, &[PREFIX_KEY_BOND_INFO, user.as_slice()]);
+    let previous = bond_bucket.may_load(&note_id.to_be_bytes())?;
+    bond_bucket.save(&note_id.to_be_bytes(), bond_info)?;
+
+    let mut summary = read_bond_summary(storage)?;
+    match previous {
+        Some(previous) => {
+            summary.total_payout = summary.total_payout.checked_sub(previous.payout)?
+        }
+        None => {
+            let mut holder_bucket: Bucket<u64> = bucket(storage, PREFIX_KEY_BOND_HOLDER);
+            let note_count = holder_bucket.may_load(user.as_slice())?.unwrap_or_default();
+            holder_bucket.save(user.as_slice(), &(note_count + 1))?;
+            if note_count == 0 {
+                summary.holder_count += 1;
+            }
+        }
+    }
+    summary.total_payout += bond_info.payout;
+
+    singleton(storage, KEY_BOND_SUMMARY).save(&summary)
+}
+
+pub fn read_bond_info(
+    storage: &dyn Storage,
+    user: CanonicalAddr,
+    note_id: u64,
+) -> StdResult<BondInfo> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_KEY_BOND_INFO, user.as_slice()])
+        .load(&note_id.to_be_bytes())
+}
+
+pub fn read_note_depositor(storage: &dyn Storage, note_id: u64) -> StdResult<CanonicalAddr> {
+    bucket_read(storage, PREFIX_KEY_NOTE_DEPOSITOR).load(&note_id.to_be_bytes())
+}
+
+pub fn read_bond_infos(
+    storage: &dyn Storage,
+    user: CanonicalAddr,
+) -> StdResult<Vec<(u64, BondInfo)>> {
+    let bond_bucket: ReadonlyBucket<BondInfo> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_KEY_BOND_INFO, user.as_slice()]);
+
+    bond_bucket
+        .range(None, None, Order::Ascending)
+        .map(|item| {
+            let (key, bond_info) = item?;
+            let mut note_id = [0u8; 8];
+            note_id.copy_from_slice(&key);
+            Ok((u64::from_be_bytes(note_id), bond_info))
+        })
+        .collect()
+}
+
+pub fn remove_bond_info(
+    storage: &mut dyn Storage,
+    user: CanonicalAddr,
+    note_id: u64,
+) -> StdResult<()> {
+    let mut bond_bucket: Bucket<BondInfo> =
+        Bucket::multilevel(storage, &[PREFIX_KEY_BOND_INFO, user.as_slice()]);
+    let previous = match bond_bucket.may_load(&note_id.to_be_bytes())? {
+        Some(previous) => previous,
+        None => return Ok(()),
+    };
+    bond_bucket.remove(&note_id.to_be_bytes());
+
+    let mut summary = read_bond_summary(storage)?;
+    summary.total_payout = summary.total_payout.checked_sub(previous.payout)?;
+
+    let mut holder_bucket: Bucket<u64> = bucket(storage, PREFIX_KEY_BOND_HOLDER);
+    let note_count = holder_bucket.load(user.as_slice())? - 1;
+    if note_count == 0 {
+        holder_bucket.remove(user.as_slice());
+        summary.holder_count -= 1;
+    } else {
+        holder_bucket.save(user.as_slice(), &note_count)?;
+    }
+
+    singleton(storage, KEY_BOND_SUMMARY).save(&summary)
+}
+
+pub fn read_bond_summary(storage: &dyn Storage) -> StdResult<BondSummary> {
+    Ok(singleton_read(storage, KEY_BOND_SUMMARY)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+/// A depositor and its notes
+pub type HolderNotes = (CanonicalAddr, Vec<(u64, BondInfo)>);
+
+/// Notes grouped by depositor, ordered by canonical address then note id. `limit` counts notes,
+/// so the notes of a depositor can continue on the next page after `start_after_note_id`
+pub fn read_bond_holder_notes(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    start_after_note_id: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<HolderNotes>> {
+    let mut remaining = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // the holder of `start_after_note_id` is read again for its remaining notes
+    let start = start_after.clone().map(|start_after| {
+        let mut start = start_after.as_slice().to_vec();
+        if start_after_note_id.is_none() {
+            start.push(0);
+        }
+        start
+    });
+
+    let holder_bucket: ReadonlyBucket<u64> = bucket_read(storage, PREFIX_KEY_BOND_HOLDER);
+    let mut holder_notes: Vec<HolderNotes> = vec![];
+    for item in holder_bucket.range(start.as_deref(), None, Order::Ascending) {
+        if remaining == 0 {
+            break;
+        }
+
+        let user = CanonicalAddr::from(item?.0);
+        let note_start = match start_after_note_id {
+            Some(note_id) if Some(&user) == start_after.as_ref() => match note_id.checked_add(1) {
+                Some(note_start) => Some(note_start.to_be_bytes()),
+                None => continue,
+            },
+            _ => None,
+        };
+
+        let bond_bucket: ReadonlyBucket<BondInfo> =
+            ReadonlyBucket::multilevel(storage, &[PREFIX_KEY_BOND_INFO, user.as_slice()]);
+        let notes = bond_bucket
+            .range(
+                note_start.as_ref().map(|start| start.as_slice()),
+                None,
+                Order::Ascending,
+            )
+            .take(remaining)
+            .map(|item| {
+                let (key, bond_info) = item?;
+                let mut note_id = [0u8; 8];
+                note_id.copy_from_slice(&key);
+                Ok((u64::from_be_bytes(note_id), bond_info))
+            })
+            .collect::<StdResult<Vec<(u64, BondInfo)>>>()?;
+
+        if !notes.is_empty() {
+            remaining -= notes.len();
+            holder_notes.push((user, notes));
+        }
+    }
+
+    Ok(holder_notes)
+}
+
+pub fn store_depositor_approval(
+    storage: &mut dyn Storage,
+    user: &CanonicalAddr,
+    operator: &CanonicalAddr,
+    approved: &bool,
 ) -> StdResult<()> {
-    bucket(storage, PREFIX_KEY_BOND_INFO).save(&user.as_slice(), bond_info)
+    Bucket::multilevel(storage, &[PREFIX_KEY_DEPOSITOR_APPROVAL, user.as_slice()])
+        .save(operator.as_slice(), approved)
+}
+
+pub fn read_depositor_approval(
+    storage: &dyn Storage,
+    user: &CanonicalAddr,
+    operator: &CanonicalAddr,
+) -> StdResult<bool> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_KEY_DEPOSITOR_APPROVAL, user.as_slice()])
+        .load(operator.as_slice())
+}
+
+pub fn store_open_deposit(
+    storage: &mut dyn Storage,
+    user: &CanonicalAddr,
+    open: &bool,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_KEY_OPEN_DEPOSIT).save(user.as_slice(), open)
+}
+
+pub fn read_open_deposit(storage: &dyn Storage, user: &CanonicalAddr) -> StdResult<bool> {
+    bucket_read(storage, PREFIX_KEY_OPEN_DEPOSIT).load(user.as_slice())
+}
+
+pub fn store_new_pending_terms(
+    storage: &mut dyn Storage,
+    pending_terms: &PendingTerms,
+) -> StdResult<u64> {
+    let id: u64 = singleton_read(storage, KEY_PENDING_TERMS_COUNT)
+        .may_load()?
+        .unwrap_or_default();
+
+    bucket(storage, PREFIX_KEY_PENDING_TERMS).save(&id.to_be_bytes(), pending_terms)?;
+
+    singleton(storage, KEY_PENDING_TERMS_COUNT).save(&(id + 1))?;
+
+    Ok(id)
+}
+
+pub fn read_pending_terms(storage: &dyn Storage, id: u64) -> StdResult<Option<PendingTerms>> {
+    bucket_read(storage, PREFIX_KEY_PENDING_TERMS).may_load(&id.to_be_bytes())
+}
+
+pub fn read_all_pending_terms(storage: &dyn Storage) -> StdResult<Vec<(u64, PendingTerms)>> {
+    let pending_bucket: ReadonlyBucket<PendingTerms> =
+        bucket_read(storage, PREFIX_KEY_PENDING_TERMS);
+
+    pending_bucket
+        .range(None, None, Order::Ascending)
+        .map(|item| {
+            let (key, pending_terms) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&key);
+            Ok((u64::from_be_bytes(id), pending_terms))
+        })
+        .collect()
+}
+
+pub fn remove_pending_terms(storage: &mut dyn Storage, id: u64) {
+    let mut pending_bucket: Bucket<PendingTerms> = bucket(storage, PREFIX_KEY_PENDING_TERMS);
+    pending_bucket.remove(&id.to_be_bytes());
+}
+
+pub fn store_referrer_fees(
+    storage: &mut dyn Storage,
+    referrer: &CanonicalAddr,
+    total_fees: &Uint128,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_KEY_REFERRER_FEES).save(referrer.as_slice(), total_fees)
+}
+
+pub fn read_referrer_fees(storage: &dyn Storage, referrer: &CanonicalAddr) -> StdResult<Uint128> {
+    Ok(bucket_read(storage, PREFIX_KEY_REFERRER_FEES)
+        .may_load(referrer.as_slice())?
+        .unwrap_or_default())
+}
+
+pub fn store_temp_deposit(storage: &mut dyn Storage, temp_deposit: &TempDeposit) -> StdResult<()> {
+    singleton(storage, KEY_TEMP_DEPOSIT).save(temp_deposit)
 }
 
-pub fn read_bond_info(storage: &dyn Storage, user: CanonicalAddr) -> StdResult<BondInfo> {
-    bucket_read(storage, PREFIX_KEY_BOND_INFO).load(&user.as_slice())
+pub fn read_temp_deposit(storage: &dyn Storage) -> StdResult<TempDeposit> {
+    singleton_read(storage, KEY_TEMP_DEPOSIT).load()
 }
 
-pub fn remove_bond_info(storage: &mut dyn Storage, user: CanonicalAddr) {
-    let mut bond_bucket: Bucket<BondInfo> = bucket(storage, PREFIX_KEY_BOND_INFO);
-    bond_bucket.remove(&user.as_slice());
+pub fn remove_temp_deposit(storage: &mut dyn Storage) {
+    let mut store: Singleton<TempDeposit> = singleton(storage, KEY_TEMP_DEPOSIT);
+    store.remove();
 }
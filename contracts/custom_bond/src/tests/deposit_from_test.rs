@@ -0,0 +1,163 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, ContractResult, CosmosMsg, Decimal, Fraction, Reply, ReplyOn,
+    SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use std::str::FromStr;
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use olympus_pro::custom_bond::{Cw20HookMsg, ExecuteMsg, QueryMsg, State};
+use terraswap::asset::AssetInfo;
+
+use crate::{
+    contract::{execute, query, reply},
+    error::ContractError,
+    tests::{
+        mock_querier::mock_dependencies,
+        test_utils::{
+            increase_time, initialize_bond, instantiate_custom_bond_measuring_received_balance,
+            instantiate_custom_bond_with_principal_token,
+        },
+    },
+};
+
+#[test]
+fn test_receive_deposit_fails_if_received_balance_is_measured() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_measuring_received_balance(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::ReceivedBalanceNotMeasured {});
+}
+
+#[test]
+fn test_deposit_from_fails_for_native_principal() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    )
+    .unwrap();
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::DepositFrom {
+        amount: Uint128::from(100000u128),
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::NotSupportNative {});
+}
+
+#[test]
+fn test_deposit_from_bonds_received_balance() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_measuring_received_balance(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    // dust left by an earlier deposit does not count towards this one
+    deps.querier.with_token_balances(&[(
+        &String::from("principal_token"),
+        &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::from(500u128))],
+    )]);
+
+    let amount = Uint128::from(100000u128);
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::DepositFrom {
+        amount,
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            id: 2,
+            gas_limit: None,
+            msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("principal_token"),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: String::from("depositor"),
+                    recipient: String::from(MOCK_CONTRACT_ADDR),
+                    amount,
+                })
+                .unwrap(),
+            }),
+            reply_on: ReplyOn::Success,
+        }]
+    );
+
+    // the principal burns 2% on transfer
+    let received = Uint128::from(98000u128);
+    deps.querier.with_token_balances(&[(
+        &String::from("principal_token"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &(Uint128::from(500u128) + received),
+        )],
+    )]);
+
+    let reply_msg = Reply {
+        id: 2,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+
+    let res = reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+    let payout = received * terms.minimum_price.inv().unwrap();
+    assert_eq!(
+        res.attributes[0..3].to_vec(),
+        vec![
+            attr("action", "deposit"),
+            attr("amount", received.to_string()),
+            attr("payout", payout.to_string()),
+        ]
+    );
+
+    let res = query(deps.as_ref(), env, QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(received, state.total_principal_bonded);
+}
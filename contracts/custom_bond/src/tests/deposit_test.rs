@@ -1,14 +1,16 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Coin, CosmosMsg, Decimal, Fraction, StdError, SubMsg, Uint128,
+    attr, from_binary, to_binary, BankMsg, Coin, CosmosMsg, Decimal, Fraction, SubMsg, Uint128,
     WasmMsg,
 };
 use std::str::FromStr;
 
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use olympus_pro::{
+    bonding_calculator::BondingCalculatorError,
     custom_bond::{
-        Adjustment, BondInfo, BondInfoResponse, Cw20HookMsg, ExecuteMsg, QueryMsg, State,
+        Adjustment, BondInfo, BondInfoResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, QueryMsg,
+        State,
     },
     custom_treasury::ExecuteMsg as CustomTreasuryExecuteMsg,
 };
@@ -16,11 +18,14 @@ use terraswap::asset::AssetInfo;
 
 use crate::{
     contract::{execute, query},
+    error::ContractError,
     tests::{
         mock_querier::mock_dependencies,
         test_utils::{
-            increase_time, initialize_bond, instantiate_custom_bond,
-            instantiate_custom_bond_with_principal_token,
+            deposit, increase_time, initialize_bond, initialize_fixed_expiry_bond,
+            instantiate_custom_bond, instantiate_custom_bond_with_lp_principal,
+            instantiate_custom_bond_with_native_payout,
+            instantiate_custom_bond_with_principal_token, set_price_guard,
         },
     },
 };
@@ -51,10 +56,14 @@ fn test_deposit_fails_if_denom_amount_is_zero() {
     let msg = ExecuteMsg::Deposit {
         max_price: Decimal::from_str("0.17476").unwrap(),
         depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("amount is zero"));
+    assert_eq!(res, ContractError::AmountIsZero {});
 }
 
 #[test]
@@ -89,10 +98,14 @@ fn test_deposit_fails_if_several_denom_received() {
     let msg = ExecuteMsg::Deposit {
         max_price: Decimal::from_str("0.17476").unwrap(),
         depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("invalid denom received"));
+    assert_eq!(res, ContractError::InvalidDenom {});
 }
 
 #[test]
@@ -106,17 +119,21 @@ fn test_deposit_fails_if_token_amount_is_zero() {
 
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount: Uint128::zero(),
     });
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("amount is zero"));
+    assert_eq!(res, ContractError::AmountIsZero {});
 }
 
 #[test]
@@ -130,17 +147,21 @@ fn test_deposit_fails_if_received_token_is_invalid() {
 
     let info = mock_info("invalid_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount: Uint128::from(100u128),
     });
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("invalid cw20 token"));
+    assert_eq!(res, ContractError::InvalidCw20Token {});
 }
 
 #[test]
@@ -157,10 +178,14 @@ fn test_deposit_fails_if_true_bond_price_is_greater_than_max_price() {
 
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.14").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount: Uint128::from(100000000u128),
@@ -169,7 +194,10 @@ fn test_deposit_fails_if_true_bond_price_is_greater_than_max_price() {
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
     assert_eq!(
         res,
-        StdError::generic_err("slippage limit: more than max price")
+        ContractError::MaxPriceExceeded {
+            price: Decimal::from_str("0.157284").unwrap(),
+            max_price: Decimal::from_str("0.14").unwrap(),
+        }
     );
 }
 
@@ -187,17 +215,27 @@ fn test_deposit_fails_if_payout_is_too_small() {
 
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount: Uint128::from(1000u128),
     });
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("bond too small"));
+    assert_eq!(
+        res,
+        ContractError::BondTooSmall {
+            payout: Uint128::from(6357u128),
+            min: Uint128::from(10000u128),
+        }
+    );
 }
 
 #[test]
@@ -214,17 +252,27 @@ fn test_deposit_fails_if_payout_is_too_large() {
 
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount: Uint128::from(4000000u128),
     });
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("bond too large"));
+    assert_eq!(
+        res,
+        ContractError::BondTooLarge {
+            payout: Uint128::from(25431703u128),
+            max: Uint128::from(20000000u128),
+        }
+    );
 }
 
 #[test]
@@ -241,17 +289,27 @@ fn test_deposit_fails_if_max_payout_reached() {
 
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount: Uint128::from(1000000u128),
     });
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("max capacity reached"));
+    assert_eq!(
+        res,
+        ContractError::MaxCapacityReached {
+            debt: Uint128::from(1012499u128),
+            max_debt: Uint128::from(300000u128),
+        }
+    );
 }
 
 #[test]
@@ -273,10 +331,14 @@ fn test_first_deposit() {
     let amount = Uint128::from(100000u128);
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount,
@@ -294,6 +356,7 @@ fn test_first_deposit() {
             attr("action", "deposit"),
             attr("amount", amount.to_string()),
             attr("payout", payout.to_string()),
+            attr("note_id", "0"),
             attr(
                 "expires",
                 (env.block.time.seconds() + terms.vesting_term).to_string()
@@ -305,11 +368,23 @@ fn test_first_deposit() {
 
     assert_eq!(
         res.messages,
-        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: String::from("custom_treasury"),
-            funds: vec![],
-            msg: to_binary(&CustomTreasuryExecuteMsg::SendPayoutTokens { amount: payout }).unwrap(),
-        })),]
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("custom_treasury"),
+                funds: vec![],
+                msg: to_binary(&CustomTreasuryExecuteMsg::SendPayoutTokens { amount: payout })
+                    .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("principal_token"),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("custom_treasury"),
+                    amount,
+                })
+                .unwrap(),
+            })),
+        ]
     );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
@@ -323,6 +398,7 @@ fn test_first_deposit() {
             payout_since_last_subsidy: payout,
             total_principal_bonded: amount,
             total_payout_given: payout,
+            paused: false,
         },
         state
     );
@@ -335,14 +411,923 @@ fn test_first_deposit() {
         },
     )
     .unwrap();
-    let bond_info: BondInfoResponse = from_binary(&res).unwrap();
+    let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
     assert_eq!(
-        BondInfo {
-            payout,
-            vesting: terms.vesting_term,
-            last_time: env.block.time.seconds(),
-            true_price_paid: terms.minimum_price,
+        vec![BondInfoResponse {
+            note_id: 0,
+            info: BondInfo {
+                payout,
+                vesting: terms.vesting_term,
+                last_time: env.block.time.seconds(),
+                true_price_paid: terms.minimum_price,
+            },
+            pending_payout: Uint128::zero(),
+        }],
+        bond_infos
+    );
+}
+
+#[test]
+fn test_deposit_with_native_payout_uses_bank_supply() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_native_payout(&mut deps).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        AssetInfo::NativeToken {
+            denom: String::from("uusd"),
+        },
+        config.payout_token
+    );
+
+    let mut env = mock_env();
+    let (terms, initial_debt) = initialize_bond(&mut deps, env.clone());
+
+    let time_increase = 100u64;
+
+    increase_time(&mut env, time_increase);
+
+    let debt_decay =
+        initial_debt * Decimal::from_ratio(time_increase as u128, terms.vesting_term as u128);
+
+    let amount = Uint128::from(100000u128);
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    let total_supply = Uint128::from(500000000000u128);
+    let total_debt = initial_debt - debt_decay + amount;
+    let debt_ratio = Decimal::from_ratio(total_debt, total_supply);
+    let payout = amount * terms.minimum_price.inv().unwrap();
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(res.attributes[2], attr("payout", payout.to_string()));
+    assert_eq!(
+        res.attributes[6],
+        attr("debt_ratio", debt_ratio.to_string())
+    );
+}
+
+#[test]
+fn test_second_deposit_creates_new_note() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let first_bond_info = deposit(&mut deps, env.clone());
+
+    let time_increase = 10000u64;
+    increase_time(&mut env, time_increase);
+
+    let second_bond_info = deposit(&mut deps, env.clone());
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![
+            BondInfoResponse {
+                note_id: 0,
+                info: first_bond_info.clone(),
+                pending_payout: first_bond_info.payout
+                    * Decimal::from_ratio(time_increase as u128, terms.vesting_term as u128),
+            },
+            BondInfoResponse {
+                note_id: 1,
+                info: BondInfo {
+                    payout: second_bond_info.payout,
+                    vesting: terms.vesting_term,
+                    last_time: env.block.time.seconds(),
+                    true_price_paid: second_bond_info.true_price_paid,
+                },
+                pending_payout: Uint128::zero(),
+            },
+        ],
+        bond_infos
+    );
+}
+
+#[test]
+fn test_deposit_fails_if_depositor_not_approved() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let bond_info = deposit(&mut deps, env.clone());
+
+    increase_time(&mut env, 10000u64);
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "attacker".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::DepositorNotApproved {});
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(1, bond_infos.len());
+    assert_eq!(bond_info, bond_infos[0].info);
+}
+
+#[test]
+fn test_deposit_by_approved_operator() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::ApproveDepositor {
+        operator: Some(String::from("operator")),
+    };
+
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info(
+        "operator",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100000u128),
+        }],
+    );
+    let msg = ExecuteMsg::Deposit {
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(1, bond_infos.len());
+
+    let revoke_info = mock_info("depositor", &[]);
+    let revoke_msg = ExecuteMsg::RevokeDepositor {
+        operator: Some(String::from("operator")),
+    };
+
+    execute(deps.as_mut(), env.clone(), revoke_info, revoke_msg).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::DepositorNotApproved {});
+}
+
+#[test]
+fn test_deposit_by_anyone_if_depositor_opted_in() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::ApproveDepositor { operator: None };
+
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(1, bond_infos.len());
+}
+
+#[test]
+fn test_deposit_forwards_native_principal() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
         },
-        bond_info.info
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let amount = Uint128::from(100000u128);
+    let info = mock_info(
+        "depositor",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount,
+        }],
     );
+    let msg = ExecuteMsg::Deposit {
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("custom_treasury"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount,
+            }],
+        }))
+    );
+}
+
+#[test]
+fn test_deposit_bonds_native_principal_net_of_tax() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let amount = Uint128::from(100000u128);
+    let info = mock_info(
+        "depositor",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount,
+        }],
+    );
+    let msg = ExecuteMsg::Deposit {
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    // 1% tax comes out of the forwarded principal
+    let received = amount.multiply_ratio(100u128, 101u128);
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("custom_treasury"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: received,
+            }],
+        }))
+    );
+
+    let res = query(deps.as_ref(), env, QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(received, state.total_principal_bonded);
+}
+
+#[test]
+fn test_fixed_expiry_deposit_vests_until_vesting_end() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let vesting_end = env.block.time.seconds() + 864000;
+    let (terms, initial_debt) = initialize_fixed_expiry_bond(&mut deps, env.clone(), vesting_end);
+
+    let time_increase = 432000u64;
+    increase_time(&mut env, time_increase);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::CurrentDebt {}).unwrap();
+    let current_debt: Uint128 = from_binary(&res).unwrap();
+    assert_eq!(
+        initial_debt - initial_debt * Decimal::from_ratio(time_increase, 864000u64),
+        current_debt
+    );
+
+    let bond_info = deposit(&mut deps, env.clone());
+    assert_eq!(
+        BondInfo {
+            payout: bond_info.payout,
+            vesting: vesting_end - env.block.time.seconds(),
+            last_time: env.block.time.seconds(),
+            true_price_paid: terms.minimum_price,
+        },
+        bond_info
+    );
+}
+
+#[test]
+fn test_deposit_fails_if_fixed_expiry_bond_expired() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let vesting_end = env.block.time.seconds() + 864000;
+    initialize_fixed_expiry_bond(&mut deps, env.clone(), vesting_end);
+
+    increase_time(&mut env, 864000);
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::BondExpired {});
+}
+
+fn deposit_msg() -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    })
+}
+
+#[test]
+fn test_deposit_within_price_guard_band() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    // market price 0.2, bond price 0.157284 is a 21.36% discount
+    set_price_guard(
+        &mut deps,
+        Decimal::percent(10),
+        Decimal::percent(30),
+        Uint128::from(200000u128),
+    );
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    execute(deps.as_mut(), env, info, deposit_msg()).unwrap();
+}
+
+#[test]
+fn test_deposit_fails_if_discount_is_below_price_guard_minimum() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    set_price_guard(
+        &mut deps,
+        Decimal::percent(25),
+        Decimal::percent(30),
+        Uint128::from(200000u128),
+    );
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    let res = execute(deps.as_mut(), env, info, deposit_msg()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::DiscountBelowMinimum {
+            bond_price: Decimal::from_str("0.157284").unwrap(),
+            max_price: Decimal::from_str("0.15").unwrap(),
+        }
+    );
+}
+
+#[test]
+fn test_deposit_fails_if_discount_is_above_price_guard_maximum() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    set_price_guard(
+        &mut deps,
+        Decimal::percent(10),
+        Decimal::percent(20),
+        Uint128::from(200000u128),
+    );
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    let res = execute(deps.as_mut(), env, info, deposit_msg()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::DiscountAboveMaximum {
+            bond_price: Decimal::from_str("0.157284").unwrap(),
+            min_price: Decimal::from_str("0.16").unwrap(),
+        }
+    );
+}
+
+#[test]
+fn test_deposit_fails_if_bond_price_is_above_market_price() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    // market price 0.1
+    set_price_guard(
+        &mut deps,
+        Decimal::zero(),
+        Decimal::percent(30),
+        Uint128::from(100000u128),
+    );
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    let res = execute(deps.as_mut(), env, info, deposit_msg()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::BondPriceAboveMarket {
+            bond_price: Decimal::from_str("0.157284").unwrap(),
+            market_price: Decimal::from_str("0.1").unwrap(),
+        }
+    );
+}
+
+#[test]
+fn test_deposit_values_lp_principal_at_risk_free_value() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_lp_principal(&mut deps, "lp_token").unwrap();
+
+    let mut env = mock_env();
+    let (terms, initial_debt) = initialize_bond(&mut deps, env.clone());
+
+    let time_increase = 100u64;
+    increase_time(&mut env, time_increase);
+
+    let debt_decay =
+        initial_debt * Decimal::from_ratio(time_increase as u128, terms.vesting_term as u128);
+
+    let amount = Uint128::from(100000u128);
+    let info = mock_info("lp_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // 2 * sqrt(1000 * 250) = 1000 payout tokens of value back 500 LP tokens
+    let value = Uint128::from(200000u128);
+    let payout = value * terms.minimum_price.inv().unwrap();
+
+    let bond_info = query(deps.as_ref(), env.clone(), QueryMsg::Note { note_id: 0 }).unwrap();
+    let bond_info: BondInfoResponse = from_binary(&bond_info).unwrap();
+    assert_eq!(payout, bond_info.info.payout);
+
+    let res = query(deps.as_ref(), env, QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(initial_debt - debt_decay + value, state.total_debt);
+    assert_eq!(amount, state.total_principal_bonded);
+}
+
+#[test]
+fn test_deposit_fails_if_lp_decimals_overflow() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_lp_principal(&mut deps, "lp_token").unwrap();
+    initialize_bond(&mut deps, mock_env());
+
+    deps.querier.with_token_info(
+        &[(
+            &String::from("payout_token"),
+            &Uint128::from(1000000000000u128),
+        )],
+        &[
+            (&String::from("lp_token"), &6u8),
+            (&String::from("payout_token"), &33u8),
+        ],
+    );
+
+    let info = mock_info("lp_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::BondingCalculator(BondingCalculatorError::DecimalsOverflow { exp: 39 })
+    );
+}
+
+#[test]
+fn test_partial_deposit_fills_up_to_max_debt() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, initial_debt) = initialize_bond(&mut deps, env.clone());
+
+    let time_increase = 100u64;
+    increase_time(&mut env, time_increase);
+
+    let debt_decay =
+        initial_debt * Decimal::from_ratio(time_increase as u128, terms.vesting_term as u128);
+
+    let amount = Uint128::from(1000000u128);
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: true,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    let filled = terms.max_debt - (initial_debt - debt_decay);
+    let refunded = amount - filled;
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.attributes[1..4],
+        vec![
+            attr("amount", amount.to_string()),
+            attr("filled", filled.to_string()),
+            attr("refunded", refunded.to_string()),
+        ]
+    );
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("principal_token"),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("depositor"),
+                amount: refunded,
+            })
+            .unwrap(),
+        }))
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(terms.max_debt, state.total_debt);
+    assert_eq!(filled, state.total_principal_bonded);
+}
+
+#[test]
+fn test_partial_deposit_fills_up_to_max_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: None,
+        max_payout: None,
+        max_debt: Some(Uint128::from(1000000000000u128)),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    increase_time(&mut env, 100u64);
+
+    let amount = Uint128::from(400000000u128);
+    let info = mock_info(
+        "depositor",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount,
+        }],
+    );
+    let msg = ExecuteMsg::Deposit {
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: true,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    // max payout is 2000000000, worth 314568000 uusd at the minimum price
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.attributes[1..5],
+        vec![
+            attr("amount", amount.to_string()),
+            attr("filled", "314568000"),
+            attr("refunded", "85432000"),
+            attr("payout", "1999999999"),
+        ]
+    );
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("depositor"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(85432000u128),
+            }],
+        }))
+    );
+}
+
+#[test]
+fn test_partial_deposit_fails_if_bond_is_full() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: None,
+        max_payout: None,
+        max_debt: Some(Uint128::from(10000u128)),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: true,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::MaxCapacityReached {
+            debt: Uint128::from(112499u128),
+            max_debt: Uint128::from(10000u128),
+        }
+    );
+}
+
+#[test]
+fn test_deposit_fails_if_deadline_exceeded() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let deadline = env.block.time.seconds() + 50;
+    increase_time(&mut env, 100u64);
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: Some(deadline),
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::DeadlineExceeded { deadline });
+}
+
+#[test]
+fn test_deposit_fails_if_payout_is_less_than_min_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let amount = Uint128::from(100000u128);
+    let payout = amount * terms.minimum_price.inv().unwrap();
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: Some(payout + Uint128::from(1u128)),
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::MinPayoutNotMet {
+            payout,
+            min_payout: payout + Uint128::from(1u128),
+        }
+    );
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: Some(payout),
+            deadline: Some(env.block.time.seconds()),
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(res.attributes[2], attr("payout", payout.to_string()));
 }
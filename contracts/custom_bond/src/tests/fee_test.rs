@@ -0,0 +1,328 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, BankMsg, Coin, CosmosMsg, Decimal, Env, Fraction, OwnedDeps,
+    Response, SubMsg, Uint128, WasmMsg,
+};
+use std::str::FromStr;
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use olympus_pro::custom_bond::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, FeeRecipient, FeeTier, QueryMsg, ReferrerResponse,
+};
+
+use crate::{
+    contract::{execute, query},
+    error::ContractError,
+    tests::{
+        mock_querier::{mock_dependencies, WasmMockQuerier},
+        test_utils::{
+            increase_time, initialize_bond, instantiate_custom_bond,
+            instantiate_custom_bond_with_native_payout,
+        },
+    },
+};
+
+#[test]
+fn test_update_fee_recipients_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdateFeeRecipients {
+        fee_recipients: vec![],
+        referrer_share: Decimal::percent(10),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_update_fee_recipients_by_olympus_dao() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("olympus_dao", &[]);
+    let msg = ExecuteMsg::UpdateFeeRecipients {
+        fee_recipients: vec![FeeRecipient {
+            address: String::from("integrator"),
+            weight: 0,
+        }],
+        referrer_share: Decimal::percent(10),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidFeeRecipientWeight {});
+
+    let msg = ExecuteMsg::UpdateFeeRecipients {
+        fee_recipients: vec![
+            FeeRecipient {
+                address: String::from("integrator"),
+                weight: u64::MAX,
+            },
+            FeeRecipient {
+                address: String::from("frontend"),
+                weight: 1,
+            },
+        ],
+        referrer_share: Decimal::percent(10),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(res, ContractError::FeeRecipientWeightOverflow {});
+
+    let msg = ExecuteMsg::UpdateFeeRecipients {
+        fee_recipients: vec![],
+        referrer_share: Decimal::percent(101),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InvalidReferrerShare {
+            referrer_share: Decimal::percent(101)
+        }
+    );
+
+    let fee_recipients = vec![
+        FeeRecipient {
+            address: String::from("olympus_treasury"),
+            weight: 2,
+        },
+        FeeRecipient {
+            address: String::from("integrator"),
+            weight: 1,
+        },
+    ];
+    let msg = ExecuteMsg::UpdateFeeRecipients {
+        fee_recipients: fee_recipients.clone(),
+        referrer_share: Decimal::percent(10),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "update_fee_recipients")]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(fee_recipients, config.fee_recipients);
+    assert_eq!(Decimal::percent(10), config.referrer_share);
+}
+
+#[test]
+fn test_deposit_splits_fee_between_referrer_and_recipients() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+    set_fee(
+        &mut deps,
+        vec![
+            FeeRecipient {
+                address: String::from("olympus_treasury"),
+                weight: 2,
+            },
+            FeeRecipient {
+                address: String::from("integrator"),
+                weight: 1,
+            },
+        ],
+    );
+
+    increase_time(&mut env, 100);
+
+    let amount = Uint128::from(100000u128);
+    let res = deposit_with_referrer(&mut deps, env.clone(), amount, "frontend").unwrap();
+
+    let total = amount * terms.minimum_price.inv().unwrap();
+    let fee = total * Decimal::percent(10);
+    let referrer_fee = fee * Decimal::percent(10);
+    let remaining = fee - referrer_fee;
+    let integrator_fee = remaining.multiply_ratio(1u128, 3u128);
+    let treasury_fee = remaining - integrator_fee;
+
+    assert_eq!(
+        res.messages[1..4].to_vec(),
+        vec![
+            payout_transfer("frontend", referrer_fee),
+            payout_transfer("olympus_treasury", treasury_fee),
+            payout_transfer("integrator", integrator_fee),
+        ]
+    );
+    assert_eq!(
+        res.attributes[7..9].to_vec(),
+        vec![
+            attr("referrer", "frontend"),
+            attr("referrer_fee", referrer_fee.to_string()),
+        ]
+    );
+
+    deposit_with_referrer(&mut deps, env, amount, "frontend").unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Referrer {
+            referrer: String::from("frontend"),
+        },
+    )
+    .unwrap();
+    let referrer: ReferrerResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        ReferrerResponse {
+            referrer: String::from("frontend"),
+            total_fees: referrer_fee + referrer_fee,
+        },
+        referrer
+    );
+}
+
+#[test]
+fn test_deposit_sends_fee_to_olympus_treasury_without_recipients() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+    set_fee(&mut deps, vec![]);
+
+    increase_time(&mut env, 100);
+
+    let amount = Uint128::from(100000u128);
+    let res = deposit_with_referrer(&mut deps, env, amount, "frontend").unwrap();
+
+    let fee = amount * terms.minimum_price.inv().unwrap() * Decimal::percent(10);
+    let referrer_fee = fee * Decimal::percent(10);
+    assert_eq!(
+        res.messages[1..3].to_vec(),
+        vec![
+            payout_transfer("frontend", referrer_fee),
+            payout_transfer("olympus_treasury", fee - referrer_fee),
+        ]
+    );
+}
+
+#[test]
+fn test_deposit_credits_referrer_with_native_fee_net_of_tax() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    instantiate_custom_bond_with_native_payout(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+    set_fee(&mut deps, vec![]);
+
+    increase_time(&mut env, 100);
+
+    let amount = Uint128::from(100000u128);
+    let res = deposit_with_referrer(&mut deps, env, amount, "frontend").unwrap();
+
+    let fee = amount * terms.minimum_price.inv().unwrap() * Decimal::percent(10);
+    let referrer_fee = (fee * Decimal::percent(10)).multiply_ratio(100u128, 101u128);
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("frontend"),
+            amount: vec![Coin {
+                denom: String::from("uusd"),
+                amount: referrer_fee,
+            }],
+        }))
+    );
+    assert_eq!(
+        res.attributes[8],
+        attr("referrer_fee", referrer_fee.to_string())
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Referrer {
+            referrer: String::from("frontend"),
+        },
+    )
+    .unwrap();
+    let referrer: ReferrerResponse = from_binary(&res).unwrap();
+    assert_eq!(referrer_fee, referrer.total_fees);
+}
+
+#[test]
+fn test_deposit_fails_on_self_referral() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+    set_fee(&mut deps, vec![]);
+
+    increase_time(&mut env, 100);
+
+    let res =
+        deposit_with_referrer(&mut deps, env, Uint128::from(100000u128), "depositor").unwrap_err();
+    assert_eq!(res, ContractError::SelfReferral {});
+}
+
+/// Charges a 10% fee in payout tokens, 10% of which goes to the referrer
+fn set_fee(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    fee_recipients: Vec<FeeRecipient>,
+) {
+    let info = mock_info("olympus_dao", &[]);
+    let msg = ExecuteMsg::UpdateFeeTiers {
+        fee_tiers: vec![FeeTier {
+            tier_ceiling: Uint128::from(1000000000u128),
+            fee_rate: Decimal::percent(10),
+        }],
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateFeeRecipients {
+        fee_recipients,
+        referrer_share: Decimal::percent(10),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+fn deposit_with_referrer(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+    amount: Uint128,
+    referrer: &str,
+) -> Result<Response, ContractError> {
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: Some(String::from(referrer)),
+        })
+        .unwrap(),
+        amount,
+    });
+
+    execute(deps.as_mut(), env, info, msg)
+}
+
+fn payout_transfer(recipient: &str, amount: Uint128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("payout_token"),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: String::from(recipient),
+            amount,
+        })
+        .unwrap(),
+    }))
+}
@@ -0,0 +1,192 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, to_binary, Decimal, Env, OwnedDeps, Uint128};
+use std::str::FromStr;
+
+use cw20::Cw20ReceiveMsg;
+use olympus_pro::custom_bond::{
+    BondInfoResponse, BondSummaryResponse, Cw20HookMsg, ExecuteMsg, HolderBondInfosResponse,
+    QueryMsg,
+};
+
+use crate::{
+    contract::{execute, query},
+    tests::{
+        mock_querier::{mock_dependencies, WasmMockQuerier},
+        test_utils::{increase_time, initialize_bond, instantiate_custom_bond},
+    },
+};
+
+#[test]
+fn test_all_bond_infos_paginates_notes() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    deposit_for(&mut deps, env.clone(), "alice");
+    deposit_for(&mut deps, env.clone(), "alice");
+    deposit_for(&mut deps, env.clone(), "bob");
+
+    // the notes of a holder continue on the next page
+    let mut pages: Vec<Vec<HolderBondInfosResponse>> = vec![];
+    let mut start_after: Option<(String, u64)> = None;
+    loop {
+        let page = query_all_bond_infos(&deps, env.clone(), start_after, Some(1));
+        let last = match page.last() {
+            Some(last) => last,
+            None => break,
+        };
+        assert_eq!(1, page.len());
+        assert_eq!(1, last.bond_infos.len());
+        start_after = Some((last.user.clone(), last.bond_infos[0].note_id));
+        pages.push(page);
+    }
+    assert_eq!(3, pages.len());
+    assert_eq!(pages[0][0].user, pages[1][0].user);
+    assert_ne!(pages[1][0].user, pages[2][0].user);
+
+    let holders = query_all_bond_infos(&deps, env.clone(), None, None);
+    assert_eq!(2, holders.len());
+    for holder in holders {
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::BondInfo {
+                user: holder.user.clone(),
+            },
+        )
+        .unwrap();
+        let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+        assert_eq!(bond_infos, holder.bond_infos);
+        match holder.user.as_str() {
+            "alice" => assert_eq!(2, holder.bond_infos.len()),
+            "bob" => assert_eq!(1, holder.bond_infos.len()),
+            user => panic!("unexpected holder {}", user),
+        }
+    }
+
+    // a page can end in the middle of a holder and start the next one
+    let page = query_all_bond_infos(
+        &deps,
+        env.clone(),
+        Some((pages[0][0].user.clone(), pages[0][0].bond_infos[0].note_id)),
+        Some(2),
+    );
+    assert_eq!(vec![pages[1][0].clone(), pages[2][0].clone()], page);
+}
+
+#[test]
+fn test_bond_summary_tracks_holders_and_outstanding_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let alice_payout = deposit_for(&mut deps, env.clone(), "alice");
+    let bob_payout = deposit_for(&mut deps, env.clone(), "bob");
+
+    assert_eq!(
+        BondSummaryResponse {
+            holder_count: 2,
+            total_payout: alice_payout + bob_payout,
+        },
+        query_bond_summary(&deps, env.clone())
+    );
+
+    increase_time(&mut env, terms.vesting_term / 2);
+
+    let info = mock_info("alice", &[]);
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let summary = query_bond_summary(&deps, env.clone());
+    assert_eq!(2, summary.holder_count);
+    assert!(summary.total_payout > bob_payout);
+    assert!(summary.total_payout < alice_payout + bob_payout);
+
+    increase_time(&mut env, terms.vesting_term);
+
+    let info = mock_info("alice", &[]);
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        BondSummaryResponse {
+            holder_count: 1,
+            total_payout: bob_payout,
+        },
+        query_bond_summary(&deps, env.clone())
+    );
+
+    let holders = query_all_bond_infos(&deps, env, None, None);
+    assert_eq!(1, holders.len());
+    assert_eq!(String::from("bob"), holders[0].user);
+}
+
+fn deposit_for(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+    depositor: &str,
+) -> Uint128 {
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from(depositor),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from(depositor),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(10000u128),
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    let payout = res
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "payout")
+        .unwrap();
+    Uint128::from_str(&payout.value).unwrap()
+}
+
+fn query_all_bond_infos(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> Vec<HolderBondInfosResponse> {
+    let (start_after, start_after_note_id) = match start_after {
+        Some((user, note_id)) => (Some(user), Some(note_id)),
+        None => (None, None),
+    };
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::AllBondInfos {
+            start_after,
+            start_after_note_id,
+            limit,
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn query_bond_summary(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+) -> BondSummaryResponse {
+    let res = query(deps.as_ref(), env, QueryMsg::BondSummary {}).unwrap();
+    from_binary(&res).unwrap()
+}
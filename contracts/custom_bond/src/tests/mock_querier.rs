@@ -1,15 +1,24 @@
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    from_binary, from_slice, to_binary, Binary, Coin, ContractResult, Decimal, OwnedDeps, Querier,
     QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use std::collections::HashMap;
 
-use cw20::{Cw20QueryMsg, TokenInfoResponse};
-use olympus_pro::custom_treasury::{
-    ConfigResponse as CustomTreasuryConfigResponse, QueryMsg as CustomTreasuryQueryMsg,
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use cw721::{Cw721QueryMsg, OwnerOfResponse, TokensResponse};
+use olympus_pro::{
+    custom_treasury::{
+        ConfigResponse as CustomTreasuryConfigResponse, QueryMsg as CustomTreasuryQueryMsg,
+    },
+    querier::BANK_SUPPLY_OF_PATH,
 };
+use protobuf::{CodedInputStream, CodedOutputStream};
 use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+use terraswap::{
+    asset::{Asset, AssetInfo, PairInfo},
+    pair::{PoolResponse, QueryMsg as PairQueryMsg},
+};
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -30,8 +39,11 @@ pub struct WasmMockQuerier {
     base: MockQuerier<TerraQueryWrapper>,
     tax_querier: TaxQuerier,
     token_querier: TokenQuerier,
+    note_querier: NoteQuerier,
+    pair_pools: HashMap<String, (PoolResponse, String)>,
     custom_treasury: String,
-    payout_token: String,
+    payout_token: AssetInfo,
+    native_supplies: HashMap<String, Uint128>,
 }
 
 #[derive(Clone, Default)]
@@ -39,6 +51,7 @@ pub struct TokenQuerier {
     // this lets us iterate over all pairs that match the first string
     supplies: HashMap<String, Uint128>,
     decimals: HashMap<String, u8>,
+    balances: HashMap<String, HashMap<String, Uint128>>,
 }
 
 impl TokenQuerier {
@@ -46,6 +59,7 @@ impl TokenQuerier {
         TokenQuerier {
             supplies: supplies_to_map(supplies),
             decimals: decimals_to_map(decimals),
+            balances: HashMap::new(),
         }
     }
 }
@@ -66,6 +80,22 @@ pub(crate) fn decimals_to_map(array: &[(&String, &u8)]) -> HashMap<String, u8> {
     map
 }
 
+#[derive(Clone, Default)]
+pub struct NoteQuerier {
+    // token id -> owner
+    owners: HashMap<String, String>,
+}
+
+impl NoteQuerier {
+    pub fn new(owners: &[(&String, &String)]) -> Self {
+        let mut map: HashMap<String, String> = HashMap::new();
+        for (token_id, owner) in owners.iter() {
+            map.insert((*token_id).clone(), (*owner).clone());
+        }
+        NoteQuerier { owners: map }
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct TaxQuerier {
     rate: Decimal,
@@ -172,10 +202,107 @@ impl WasmMockQuerier {
                                 total_supply: total_supply.clone(),
                             })))
                         }
-                        _ => panic!("DO NOT ENTER HERE"),
+                        Ok(Cw20QueryMsg::Balance { address }) => {
+                            let balance: Uint128 = self
+                                .token_querier
+                                .balances
+                                .get(contract_addr)
+                                .and_then(|balances| balances.get(&address))
+                                .copied()
+                                .unwrap_or_default();
+                            SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                                balance,
+                            })))
+                        }
+                        _ => match from_binary(&msg) {
+                            Ok(Cw721QueryMsg::OwnerOf { token_id, .. }) => {
+                                match self.note_querier.owners.get(&token_id) {
+                                    Some(owner) => SystemResult::Ok(ContractResult::from(
+                                        to_binary(&OwnerOfResponse {
+                                            owner: owner.clone(),
+                                            approvals: vec![],
+                                        }),
+                                    )),
+                                    None => SystemResult::Ok(ContractResult::Err(
+                                        "token not found".to_string(),
+                                    )),
+                                }
+                            }
+                            Ok(Cw721QueryMsg::Tokens { owner, .. }) => {
+                                let mut tokens: Vec<String> = self
+                                    .note_querier
+                                    .owners
+                                    .iter()
+                                    .filter(|(_, token_owner)| **token_owner == owner)
+                                    .map(|(token_id, _)| token_id.clone())
+                                    .collect();
+                                tokens.sort();
+                                SystemResult::Ok(ContractResult::from(to_binary(&TokensResponse {
+                                    tokens,
+                                })))
+                            }
+                            _ => match from_binary(&msg) {
+                                Ok(PairQueryMsg::Pool {}) => {
+                                    match self.pair_pools.get(contract_addr) {
+                                        Some((pool, _)) => {
+                                            SystemResult::Ok(ContractResult::from(to_binary(pool)))
+                                        }
+                                        None => panic!("DO NOT ENTER HERE"),
+                                    }
+                                }
+                                Ok(PairQueryMsg::Pair {}) => {
+                                    match self.pair_pools.get(contract_addr) {
+                                        Some((pool, liquidity_token)) => SystemResult::Ok(
+                                            ContractResult::from(to_binary(&PairInfo {
+                                                asset_infos: [
+                                                    pool.assets[0].info.clone(),
+                                                    pool.assets[1].info.clone(),
+                                                ],
+                                                contract_addr: contract_addr.clone(),
+                                                liquidity_token: liquidity_token.clone(),
+                                            })),
+                                        ),
+                                        None => panic!("DO NOT ENTER HERE"),
+                                    }
+                                }
+                                _ => panic!("DO NOT ENTER HERE"),
+                            },
+                        },
                     },
                 }
             }
+            QueryRequest::Stargate { path, data } => {
+                if path != BANK_SUPPLY_OF_PATH {
+                    panic!("DO NOT ENTER HERE")
+                }
+
+                // QuerySupplyOfRequest { string denom = 1 }
+                let mut is = CodedInputStream::from_bytes(data.as_slice());
+                is.read_tag_unpack().unwrap();
+                let denom = is.read_string().unwrap();
+                let supply = self
+                    .native_supplies
+                    .get(&denom)
+                    .copied()
+                    .unwrap_or_default();
+
+                // QuerySupplyOfResponse { Coin amount = 1 }
+                let mut coin = vec![];
+                {
+                    let mut os = CodedOutputStream::vec(&mut coin);
+                    os.write_string(1, &denom).unwrap();
+                    os.write_string(2, &supply.to_string()).unwrap();
+                    os.flush().unwrap();
+                }
+                let mut res = vec![];
+                {
+                    let mut os = CodedOutputStream::vec(&mut res);
+                    os.write_bytes(1, &coin).unwrap();
+                    os.flush().unwrap();
+                }
+
+                SystemResult::Ok(ContractResult::Ok(Binary(res)))
+            }
             _ => self.base.handle_query(request),
         }
     }
@@ -187,8 +314,13 @@ impl WasmMockQuerier {
             base,
             tax_querier: TaxQuerier::default(),
             token_querier: TokenQuerier::default(),
+            note_querier: NoteQuerier::default(),
+            pair_pools: HashMap::new(),
             custom_treasury: String::default(),
-            payout_token: String::default(),
+            payout_token: AssetInfo::Token {
+                contract_addr: String::default(),
+            },
+            native_supplies: HashMap::new(),
         }
     }
 
@@ -205,8 +337,43 @@ impl WasmMockQuerier {
         self.token_querier = TokenQuerier::new(supplies, decimals);
     }
 
-    pub fn with_custom_treasury(&mut self, custom_treasury: String, payout_token: String) {
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        for (contract_addr, balances) in balances.iter() {
+            self.token_querier
+                .balances
+                .insert((*contract_addr).clone(), supplies_to_map(balances));
+        }
+    }
+
+    pub fn with_note_owners(&mut self, owners: &[(&String, &String)]) {
+        self.note_querier = NoteQuerier::new(owners);
+    }
+
+    pub fn with_pair_pool(
+        &mut self,
+        pair: String,
+        assets: [Asset; 2],
+        total_share: Uint128,
+        liquidity_token: String,
+    ) {
+        self.pair_pools.insert(
+            pair,
+            (
+                PoolResponse {
+                    assets,
+                    total_share,
+                },
+                liquidity_token,
+            ),
+        );
+    }
+
+    pub fn with_custom_treasury(&mut self, custom_treasury: String, payout_token: AssetInfo) {
         self.custom_treasury = custom_treasury;
         self.payout_token = payout_token;
     }
+
+    pub fn with_native_supplies(&mut self, supplies: &[(&String, &Uint128)]) {
+        self.native_supplies = supplies_to_map(supplies);
+    }
 }
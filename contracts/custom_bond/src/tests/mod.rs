@@ -1,5 +1,12 @@
+pub mod deposit_from_test;
 pub mod deposit_test;
+pub mod fee_test;
+pub mod holders_test;
 pub mod mock_querier;
+pub mod note_test;
+pub mod pause_test;
 pub mod redeem_test;
+pub mod simulate_test;
+pub mod terms_test;
 pub mod test_utils;
 pub mod update_test;
@@ -0,0 +1,363 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, ContractResult, CosmosMsg, Decimal, Fraction, Reply, ReplyOn,
+    SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use std::str::FromStr;
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use olympus_pro::{
+    custom_bond::{
+        BondInfo, BondInfoResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+        QueryMsg,
+    },
+    custom_treasury::ExecuteMsg as CustomTreasuryExecuteMsg,
+    note_nft::{ExecuteMsg as NoteNftExecuteMsg, InstantiateMsg as NoteNftInstantiateMsg, MintMsg},
+    response::MsgInstantiateContractResponse,
+};
+use protobuf::Message;
+use terraswap::asset::AssetInfo;
+
+use crate::{
+    contract::{execute, instantiate, query, reply},
+    error::ContractError,
+    tests::{
+        mock_querier::mock_dependencies,
+        test_utils::{
+            deposit, increase_time, initialize_bond, instantiate_custom_bond,
+            instantiate_custom_bond_with_note_nft, instantiate_msg,
+        },
+    },
+};
+
+#[test]
+fn test_instantiate_with_note_nft() {
+    let mut deps = mock_dependencies(&[]);
+
+    deps.querier.with_custom_treasury(
+        String::from("custom_treasury"),
+        AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
+    );
+
+    let msg = InstantiateMsg {
+        note_nft_code_id: Some(3u64),
+        ..instantiate_msg()
+    };
+
+    let info = mock_info("policy", &[]);
+
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            id: 1,
+            gas_limit: None,
+            msg: WasmMsg::Instantiate {
+                code_id: 3u64,
+                funds: vec![],
+                admin: Some(MOCK_CONTRACT_ADDR.to_string()),
+                label: "OlympusPro Bond Note".to_string(),
+                msg: to_binary(&NoteNftInstantiateMsg {
+                    name: "OlympusPro Bond Note".to_string(),
+                    symbol: "OPBN".to_string(),
+                    minter: MOCK_CONTRACT_ADDR.to_string(),
+                })
+                .unwrap(),
+            }
+            .into(),
+            reply_on: ReplyOn::Success,
+        }]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(None, config.note_nft);
+
+    let mut note_inst_res = MsgInstantiateContractResponse::new();
+    note_inst_res.set_contract_address("note_nft".to_string());
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(note_inst_res.write_to_bytes().unwrap().into()),
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "register_note_nft"),
+            attr("note_nft", "note_nft")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(Some(String::from("note_nft")), config.note_nft);
+}
+
+#[test]
+fn test_instantiate_without_note_nft() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(None, config.note_nft);
+}
+
+#[test]
+fn test_deposit_mints_note() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_note_nft(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let amount = Uint128::from(100000u128);
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    let payout = amount * terms.minimum_price.inv().unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("custom_treasury"),
+                funds: vec![],
+                msg: to_binary(&CustomTreasuryExecuteMsg::SendPayoutTokens { amount: payout })
+                    .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("note_nft"),
+                funds: vec![],
+                msg: to_binary(&NoteNftExecuteMsg::Mint(MintMsg {
+                    token_id: String::from("0"),
+                    owner: String::from("depositor"),
+                    token_uri: None,
+                    extension: BondInfo {
+                        payout,
+                        vesting: terms.vesting_term,
+                        last_time: env.block.time.seconds(),
+                        true_price_paid: terms.minimum_price,
+                    },
+                }))
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("principal_token"),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("custom_treasury"),
+                    amount,
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    let res = query(deps.as_ref(), env, QueryMsg::Note { note_id: 0 }).unwrap();
+    let note: BondInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(0, note.note_id);
+    assert_eq!(payout, note.info.payout);
+}
+
+#[test]
+fn test_redeem_note_by_owner() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_note_nft(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let bond_info = deposit(&mut deps, env.clone());
+
+    // the note was transferred to a new owner
+    deps.querier
+        .with_note_owners(&[(&String::from("0"), &String::from("buyer"))]);
+
+    let time_increase = 10000;
+    increase_time(&mut env, time_increase);
+
+    let info = mock_info("buyer", &[]);
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let payout = bond_info.payout * Decimal::from_ratio(time_increase as u128, bond_info.vesting);
+
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "redeem"), attr("amount", payout.to_string())]
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "payout_token".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("buyer"),
+                amount: payout,
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Note { note_id: 0 }).unwrap();
+    let note: BondInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(bond_info.payout - payout, note.info.payout);
+}
+
+#[test]
+fn test_redeem_note_fails_if_not_owner() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_note_nft(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    deposit(&mut deps, env.clone());
+
+    deps.querier
+        .with_note_owners(&[(&String::from("0"), &String::from("buyer"))]);
+
+    increase_time(&mut env, 10000);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem {
+        note_ids: Some(vec![0]),
+    };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_redeem_burns_fully_redeemed_note() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_note_nft(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let first_bond_info = deposit(&mut deps, env.clone());
+    let second_bond_info = deposit(&mut deps, env.clone());
+
+    deps.querier.with_note_owners(&[
+        (&String::from("0"), &String::from("buyer")),
+        (&String::from("1"), &String::from("buyer")),
+    ]);
+
+    increase_time(&mut env, terms.vesting_term);
+
+    let info = mock_info("buyer", &[]);
+    let msg = ExecuteMsg::Redeem {
+        note_ids: Some(vec![0]),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("note_nft"),
+                funds: vec![],
+                msg: to_binary(&NoteNftExecuteMsg::Burn {
+                    token_id: String::from("0"),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("payout_token"),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("buyer"),
+                    amount: first_bond_info.payout,
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the token of the redeemed note is burned
+    deps.querier
+        .with_note_owners(&[(&String::from("1"), &String::from("buyer"))]);
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("buyer"),
+        },
+    )
+    .unwrap();
+    let bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![BondInfoResponse {
+            note_id: 1,
+            info: second_bond_info.clone(),
+            pending_payout: second_bond_info.payout,
+        }],
+        bond_infos
+    );
+
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "redeem"),
+            attr("amount", second_bond_info.payout.to_string())
+        ]
+    );
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("note_nft"),
+            funds: vec![],
+            msg: to_binary(&NoteNftExecuteMsg::Burn {
+                token_id: String::from("1"),
+            })
+            .unwrap(),
+        }))
+    );
+
+    deps.querier.with_note_owners(&[]);
+
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::NothingToRedeem {});
+}
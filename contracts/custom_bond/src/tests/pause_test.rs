@@ -0,0 +1,228 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, to_binary, Coin, Decimal, Uint128};
+use std::str::FromStr;
+
+use cw20::Cw20ReceiveMsg;
+use olympus_pro::custom_bond::{ConfigResponse, Cw20HookMsg, ExecuteMsg, QueryMsg, State};
+use terraswap::asset::AssetInfo;
+
+use crate::{
+    contract::{execute, query},
+    error::ContractError,
+    tests::{
+        mock_querier::mock_dependencies,
+        test_utils::{
+            deposit, increase_time, initialize_bond, instantiate_custom_bond,
+            instantiate_custom_bond_with_principal_token,
+        },
+    },
+};
+
+#[test]
+fn test_pause_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("addr", &[]);
+    let msg = ExecuteMsg::Pause {};
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_pause_and_unpause_by_pauser() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::Pause {};
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "pause")]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert!(state.paused);
+
+    let msg = ExecuteMsg::Unpause {};
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "unpause")]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert!(!state.paused);
+}
+
+#[test]
+fn test_update_pauser_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("addr", &[]);
+    let msg = ExecuteMsg::UpdatePauser {
+        pauser: String::from("pauser"),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_update_pauser_by_policy() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePauser {
+        pauser: String::from("pauser"),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "update_pauser"), attr("pauser", "pauser")]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(String::from("pauser"), config.pauser);
+
+    // the previous pauser lost the role
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info("pauser", &[]);
+    execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Pause {}).unwrap();
+}
+
+#[test]
+fn test_cw20_deposit_fails_if_paused() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Pause {}).unwrap();
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: Decimal::from_str("0.17476").unwrap(),
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount: Uint128::from(100000u128),
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::DepositsPaused {});
+}
+
+#[test]
+fn test_native_deposit_fails_if_paused() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    )
+    .unwrap();
+
+    let env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Pause {}).unwrap();
+
+    let info = mock_info(
+        "depositor",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100000u128),
+        }],
+    );
+    let msg = ExecuteMsg::Deposit {
+        max_price: Decimal::from_str("0.17476").unwrap(),
+        depositor: String::from("depositor"),
+        allow_partial: false,
+        min_payout: None,
+        deadline: None,
+        referrer: None,
+    };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::DepositsPaused {});
+}
+
+#[test]
+fn test_redeem_while_paused() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let bond_info = deposit(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Pause {}).unwrap();
+
+    increase_time(&mut env, terms.vesting_term);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "redeem"),
+            attr("amount", bond_info.payout.to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_deposit_after_unpause() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Unpause {}).unwrap();
+
+    increase_time(&mut env, 100u64);
+
+    let bond_info = deposit(&mut deps, env);
+    assert!(!bond_info.payout.is_zero());
+}
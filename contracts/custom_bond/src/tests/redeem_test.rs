@@ -1,7 +1,7 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Coin, CosmosMsg, Decimal, Fraction, StdError, SubMsg, Uint128,
-    WasmMsg,
+    attr, from_binary, to_binary, BankMsg, Coin, CosmosMsg, Decimal, Fraction, SubMsg, Timestamp,
+    Uint128, WasmMsg,
 };
 use std::str::FromStr;
 
@@ -16,10 +16,12 @@ use terraswap::asset::AssetInfo;
 
 use crate::{
     contract::{execute, query},
+    error::ContractError,
     tests::{
         mock_querier::mock_dependencies,
         test_utils::{
-            deposit, increase_time, initialize_bond, instantiate_custom_bond,
+            deposit, increase_time, initialize_bond, initialize_fixed_expiry_bond,
+            instantiate_custom_bond, instantiate_custom_bond_with_native_payout,
             instantiate_custom_bond_with_principal_token,
         },
     },
@@ -40,10 +42,10 @@ fn test_redeem_fails_if_no_pending_payout() {
     deposit(&mut deps, env.clone());
 
     let info = mock_info("depositor", &[]);
-    let msg = ExecuteMsg::Redeem {};
+    let msg = ExecuteMsg::Redeem { note_ids: None };
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("nothing to redeem"));
+    assert_eq!(res, ContractError::NothingToRedeem {});
 }
 
 #[test]
@@ -62,7 +64,7 @@ fn test_redeem_some() {
     let bond_info = deposit(&mut deps, env.clone());
 
     let info = mock_info("depositor", &[]);
-    let msg = ExecuteMsg::Redeem {};
+    let msg = ExecuteMsg::Redeem { note_ids: None };
 
     let time_increase = 10000;
     increase_time(&mut env, time_increase);
@@ -98,9 +100,10 @@ fn test_redeem_some() {
     )
     .unwrap();
 
-    let current_bond_info: BondInfoResponse = from_binary(&res).unwrap();
+    let current_bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
     assert_eq!(
-        BondInfoResponse {
+        vec![BondInfoResponse {
+            note_id: 0,
             info: BondInfo {
                 payout: bond_info.payout - payout,
                 vesting: bond_info.vesting - time_increase,
@@ -108,8 +111,8 @@ fn test_redeem_some() {
                 true_price_paid: terms.minimum_price,
             },
             pending_payout: Uint128::zero()
-        },
-        current_bond_info
+        }],
+        current_bond_infos
     );
 }
 
@@ -129,7 +132,7 @@ fn test_redeem_all() {
     let bond_info = deposit(&mut deps, env.clone());
 
     let info = mock_info("depositor", &[]);
-    let msg = ExecuteMsg::Redeem {};
+    let msg = ExecuteMsg::Redeem { note_ids: None };
 
     increase_time(&mut env, terms.vesting_term);
 
@@ -156,12 +159,197 @@ fn test_redeem_all() {
         }))]
     );
 
-    query(
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let current_bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(Vec::<BondInfoResponse>::new(), current_bond_infos);
+}
+
+#[test]
+fn test_redeem_native_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_native_payout(&mut deps).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let bond_info = deposit(&mut deps, env.clone());
+
+    increase_time(&mut env, terms.vesting_term);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("depositor"),
+            amount: vec![Coin {
+                denom: String::from("uusd"),
+                amount: bond_info.payout,
+            }],
+        }))]
+    );
+}
+
+#[test]
+fn test_redeem_selected_notes() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    let time_increase = 100u64;
+
+    increase_time(&mut env, time_increase);
+
+    let first_bond_info = deposit(&mut deps, env.clone());
+    let second_bond_info = deposit(&mut deps, env.clone());
+
+    increase_time(&mut env, terms.vesting_term);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem {
+        note_ids: Some(vec![1]),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "redeem"),
+            attr("amount", second_bond_info.payout.to_string()),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BondInfo {
+            user: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let current_bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![BondInfoResponse {
+            note_id: 0,
+            info: first_bond_info.clone(),
+            pending_payout: first_bond_info.payout,
+        }],
+        current_bond_infos
+    );
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem {
+        note_ids: Some(vec![1]),
+    };
+
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+}
+
+#[test]
+fn test_redeem_fails_with_duplicate_note_ids() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+
+    increase_time(&mut env, 100u64);
+
+    let bond_info = deposit(&mut deps, env.clone());
+
+    increase_time(&mut env, terms.vesting_term);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem {
+        note_ids: Some(vec![0, 0, 0]),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(res, ContractError::DuplicateNoteId { note_id: 0 });
+
+    let msg = ExecuteMsg::Redeem {
+        note_ids: Some(vec![0]),
+    };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "redeem"),
+            attr("amount", bond_info.payout.to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_redeem_fixed_expiry_at_vesting_end() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let vesting_end = env.block.time.seconds() + 864000;
+    initialize_fixed_expiry_bond(&mut deps, env.clone(), vesting_end);
+
+    increase_time(&mut env, 432000);
+    let first = deposit(&mut deps, env.clone());
+
+    increase_time(&mut env, 216000);
+    let second = deposit(&mut deps, env.clone());
+
+    // halfway through the second note both notes have vested the same share
+    increase_time(&mut env, 108000);
+
+    let info = mock_info("depositor", &[]);
+    let msg = ExecuteMsg::Redeem { note_ids: None };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let payout = first.payout * Decimal::from_ratio(324000u64, 432000u64)
+        + second.payout * Decimal::percent(50);
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "redeem"), attr("amount", payout.to_string())]
+    );
+
+    env.block.time = Timestamp::from_seconds(vesting_end);
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "redeem"),
+            attr(
+                "amount",
+                (first.payout + second.payout - payout).to_string()
+            ),
+        ]
+    );
+
+    let res = query(
         deps.as_ref(),
         env.clone(),
         QueryMsg::BondInfo {
             user: String::from("depositor"),
         },
     )
-    .unwrap_err();
+    .unwrap();
+    let current_bond_infos: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert_eq!(Vec::<BondInfoResponse>::new(), current_bond_infos);
 }
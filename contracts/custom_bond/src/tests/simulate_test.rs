@@ -0,0 +1,205 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, to_binary, Decimal, Fraction, Uint128};
+use std::str::FromStr;
+
+use cw20::Cw20ReceiveMsg;
+use olympus_pro::custom_bond::{
+    Cw20HookMsg, DepositFailure, ExecuteMsg, QueryMsg, SimulateDepositResponse, State,
+};
+
+use crate::{
+    contract::{execute, query},
+    tests::{
+        mock_querier::mock_dependencies,
+        test_utils::{increase_time, initialize_bond, instantiate_custom_bond, set_price_guard},
+    },
+};
+
+#[test]
+fn test_simulate_deposit_matches_deposit() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, initial_debt) = initialize_bond(&mut deps, env.clone());
+
+    let time_increase = 100u64;
+    increase_time(&mut env, time_increase);
+
+    let debt_decay =
+        initial_debt * Decimal::from_ratio(time_increase as u128, terms.vesting_term as u128);
+
+    let amount = Uint128::from(100000u128);
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SimulateDeposit {
+            amount,
+            depositor: String::from("depositor"),
+            sender: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateDepositResponse = from_binary(&res).unwrap();
+
+    let total_debt = initial_debt - debt_decay + amount;
+    let debt_ratio = Decimal::from_ratio(total_debt, Uint128::from(1000000000000u128));
+    let payout = amount * terms.minimum_price.inv().unwrap();
+    let expires = env.block.time.seconds() + terms.vesting_term;
+    assert_eq!(
+        SimulateDepositResponse {
+            payout,
+            fee: Uint128::zero(),
+            true_price: terms.minimum_price,
+            expires,
+            debt_ratio,
+            failures: vec![],
+        },
+        simulation
+    );
+
+    // simulating leaves the state untouched
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(initial_debt, state.total_debt);
+
+    let info = mock_info("principal_token", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "depositor".to_string(),
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            max_price: simulation.true_price,
+            depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
+        })
+        .unwrap(),
+        amount,
+    });
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(res.attributes[2], attr("payout", payout.to_string()));
+    assert_eq!(res.attributes[4], attr("expires", expires.to_string()));
+    assert_eq!(
+        res.attributes[6],
+        attr("debt_ratio", debt_ratio.to_string())
+    );
+}
+
+#[test]
+fn test_simulate_deposit_reports_failed_checks() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Pause {}).unwrap();
+
+    increase_time(&mut env, 100u64);
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::SimulateDeposit {
+            amount: Uint128::from(4000000u128),
+            depositor: String::from("depositor"),
+            sender: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateDepositResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![
+            DepositFailure::DepositsPaused {},
+            DepositFailure::BondTooLarge {
+                payout: Uint128::from(25431703u128),
+                max: Uint128::from(20000000u128),
+            },
+            DepositFailure::MaxCapacityReached {
+                debt: Uint128::from(4012499u128),
+                max_debt: Uint128::from(300000u128),
+            },
+        ],
+        simulation.failures
+    );
+}
+
+#[test]
+fn test_simulate_deposit_reports_price_guard_failure() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    set_price_guard(
+        &mut deps,
+        Decimal::percent(25),
+        Decimal::percent(30),
+        Uint128::from(200000u128),
+    );
+
+    increase_time(&mut env, 100u64);
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::SimulateDeposit {
+            amount: Uint128::from(100000u128),
+            depositor: String::from("depositor"),
+            sender: String::from("depositor"),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateDepositResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        Decimal::from_str("0.157284").unwrap(),
+        simulation.true_price
+    );
+    assert_eq!(
+        vec![DepositFailure::DiscountBelowMinimum {
+            bond_price: Decimal::from_str("0.157284").unwrap(),
+            max_price: Decimal::from_str("0.15").unwrap(),
+        }],
+        simulation.failures
+    );
+}
+
+#[test]
+fn test_simulate_deposit_checks_depositor_approval() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let msg = QueryMsg::SimulateDeposit {
+        amount: Uint128::from(100000u128),
+        depositor: String::from("depositor"),
+        sender: String::from("operator"),
+    };
+
+    let res = query(deps.as_ref(), env.clone(), msg.clone()).unwrap();
+    let simulation: SimulateDepositResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![DepositFailure::DepositorNotApproved {}],
+        simulation.failures
+    );
+
+    let info = mock_info("depositor", &[]);
+    let approve_msg = ExecuteMsg::ApproveDepositor {
+        operator: Some(String::from("operator")),
+    };
+    execute(deps.as_mut(), env.clone(), info, approve_msg).unwrap();
+
+    let res = query(deps.as_ref(), env, msg).unwrap();
+    let simulation: SimulateDepositResponse = from_binary(&res).unwrap();
+    assert!(simulation.failures.is_empty());
+}
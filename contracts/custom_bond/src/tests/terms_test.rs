@@ -0,0 +1,423 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, from_slice, Decimal, Uint128};
+
+use olympus_pro::custom_bond::{
+    ConfigResponse, ExecuteMsg, PendingTermsResponse, QueryMsg, State, Terms, TermsChange,
+    VestingKind,
+};
+
+use crate::{
+    contract::{execute, query},
+    error::ContractError,
+    tests::{
+        mock_querier::mock_dependencies,
+        test_utils::{
+            increase_time, initialize_bond, initialize_fixed_expiry_bond, instantiate_custom_bond,
+            set_terms_delay, TERMS_DELAY,
+        },
+    },
+};
+
+#[test]
+fn test_set_terms_delay_applies_immediately_without_delay() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetTermsDelay {
+        terms_delay: TERMS_DELAY,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "set_terms_delay")]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(TERMS_DELAY, config.terms_delay);
+}
+
+#[test]
+fn test_initialize_bond_fails_with_delay_once_terms_are_set() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+    set_terms_delay(&mut deps);
+
+    // the first terms are not delayed
+    let env = mock_env();
+    let (terms, initial_debt) = initialize_bond(&mut deps, env.clone());
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::InitializeBond {
+        terms: Terms {
+            max_debt: Uint128::from(1u128),
+            ..terms.clone()
+        },
+        initial_debt: Uint128::zero(),
+    };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::TermsAlreadySet {});
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(terms, state.terms);
+    assert_eq!(initial_debt, state.total_debt);
+}
+
+#[test]
+fn test_set_bond_terms_is_queued_until_activation() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let (terms, _) = initialize_bond(&mut deps, env.clone());
+    set_terms_delay(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: Some(129600),
+        vesting_end: None,
+        max_payout: None,
+        max_debt: Some(Uint128::from(500000u128)),
+    };
+
+    let activation = env.block.time.seconds() + TERMS_DELAY;
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "set_bond_terms"),
+            attr("pending_terms_id", "0"),
+            attr("activation", activation.to_string()),
+        ]
+    );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(terms, state.terms);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::PendingTerms {}).unwrap();
+    let pending_terms: Vec<PendingTermsResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![PendingTermsResponse {
+            id: 0,
+            change: TermsChange::BondTerms {
+                vesting_term: Some(129600),
+                vesting_end: None,
+                max_payout: None,
+                max_debt: Some(Uint128::from(500000u128)),
+            },
+            activation,
+        }],
+        pending_terms
+    );
+
+    increase_time(&mut env, TERMS_DELAY - 1);
+
+    let info = mock_info("addr", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ApplyPendingTerms {},
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::NoPendingTermsDue {});
+
+    increase_time(&mut env, 1);
+
+    // anyone can apply due changes
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ApplyPendingTerms {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "apply_pending_terms"), attr("applied", "0"),]
+    );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(129600, state.terms.vesting_term);
+    assert_eq!(terms.max_payout, state.terms.max_payout);
+    assert_eq!(Uint128::from(500000u128), state.terms.max_debt);
+
+    let res = query(deps.as_ref(), env, QueryMsg::PendingTerms {}).unwrap();
+    let pending_terms: Vec<PendingTermsResponse> = from_binary(&res).unwrap();
+    assert!(pending_terms.is_empty());
+}
+
+#[test]
+fn test_apply_pending_terms_skips_changes_not_yet_due() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+    set_terms_delay(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetAdjustment {
+        addition: true,
+        increment: Decimal::permille(3),
+        target: Decimal::percent(20),
+        buffer: 3600,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    increase_time(&mut env, 100);
+
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: None,
+        max_payout: None,
+        max_debt: Some(Uint128::from(500000u128)),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    increase_time(&mut env, TERMS_DELAY - 100);
+
+    let info = mock_info("addr", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ApplyPendingTerms {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("applied", "0"));
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(Decimal::permille(3), state.adjustment.rate);
+    assert_eq!(env.block.time.seconds(), state.adjustment.last_time);
+    assert_eq!(Uint128::from(300000u128), state.terms.max_debt);
+
+    let res = query(deps.as_ref(), env, QueryMsg::PendingTerms {}).unwrap();
+    let pending_terms: Vec<PendingTermsResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        vec![1],
+        pending_terms.iter().map(|p| p.id).collect::<Vec<u64>>()
+    );
+}
+
+#[test]
+fn test_apply_pending_terms_drops_invalid_changes() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let vesting_end = env.block.time.seconds() + 864000;
+    initialize_fixed_expiry_bond(&mut deps, env.clone(), vesting_end);
+    set_terms_delay(&mut deps);
+
+    // valid when queued, but too close to the expiry once due
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: Some(env.block.time.seconds() + 129600),
+        max_payout: None,
+        max_debt: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: None,
+        max_payout: None,
+        max_debt: Some(Uint128::from(500000u128)),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    increase_time(&mut env, TERMS_DELAY);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr", &[]),
+        ExecuteMsg::ApplyPendingTerms {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "apply_pending_terms"),
+            attr("applied", "1"),
+            attr("dropped", "0: vesting must be longer than 36 hours"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(vesting_end, state.terms.vesting_end);
+    assert_eq!(Uint128::from(500000u128), state.terms.max_debt);
+
+    let res = query(deps.as_ref(), env, QueryMsg::PendingTerms {}).unwrap();
+    let pending_terms: Vec<PendingTermsResponse> = from_binary(&res).unwrap();
+    assert!(pending_terms.is_empty());
+}
+
+#[test]
+fn test_set_bond_terms_matches_vesting_kind() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let env = mock_env();
+    let vesting_end = env.block.time.seconds() + 864000;
+    initialize_fixed_expiry_bond(&mut deps, env.clone(), vesting_end);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: Some(129600),
+        vesting_end: None,
+        max_payout: None,
+        max_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(res, ContractError::VestingKindMismatch {});
+
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: Some(vesting_end + 86400),
+        max_payout: None,
+        max_debt: None,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(vesting_end + 86400, state.terms.vesting_end);
+
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+    initialize_bond(&mut deps, mock_env());
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: Some(vesting_end),
+        max_payout: None,
+        max_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::VestingKindMismatch {});
+}
+
+#[test]
+fn test_lowering_terms_delay_is_queued() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+    set_terms_delay(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetTermsDelay { terms_delay: 0 };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(TERMS_DELAY, config.terms_delay);
+}
+
+#[test]
+fn test_queued_terms_are_validated() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+    initialize_bond(&mut deps, mock_env());
+    set_terms_delay(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: Some(129599),
+        vesting_end: None,
+        max_payout: None,
+        max_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::VestingTooShort {});
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingTerms {}).unwrap();
+    let pending_terms: Vec<PendingTermsResponse> = from_binary(&res).unwrap();
+    assert!(pending_terms.is_empty());
+}
+
+#[test]
+fn test_cancel_pending_terms() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+    set_terms_delay(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetBondTerms {
+        vesting_term: None,
+        vesting_end: None,
+        max_payout: None,
+        max_debt: Some(Uint128::from(500000u128)),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr", &[]),
+        ExecuteMsg::CancelPendingTerms { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::CancelPendingTerms { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::PendingTermsNotFound { id: 1 });
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CancelPendingTerms { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "cancel_pending_terms"),
+            attr("pending_terms_id", "0"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingTerms {}).unwrap();
+    let pending_terms: Vec<PendingTermsResponse> = from_binary(&res).unwrap();
+    assert!(pending_terms.is_empty());
+}
+
+#[test]
+fn test_terms_without_vesting_kind_are_fixed_term() {
+    // terms stored before fixed expiry bonds existed
+    let terms: Terms = from_slice(
+        br#"{"control_variable":"0.1","vesting_term":864000,"minimum_price":"0.157284","max_payout":"0.00002","max_debt":"300000"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(VestingKind::FixedTerm, terms.vesting_kind);
+    assert_eq!(864000, terms.vesting_term);
+    assert_eq!(0, terms.vesting_end);
+}
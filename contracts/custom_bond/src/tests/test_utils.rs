@@ -1,72 +1,115 @@
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
-use cosmwasm_std::{from_binary, to_binary, Decimal, Env, OwnedDeps, StdResult, Uint128};
+use cosmwasm_std::{
+    from_binary, to_binary, ContractResult, Decimal, Env, OwnedDeps, Reply,
+    SubMsgExecutionResponse, Uint128,
+};
 use std::str::FromStr;
 
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, query, reply},
+    error::ContractError,
     tests::mock_querier::WasmMockQuerier,
 };
 use cw20::Cw20ReceiveMsg;
-use olympus_pro::custom_bond::{
-    BondInfo, BondInfoResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, Terms,
+use olympus_pro::{
+    bonding_calculator::BondingCalculator,
+    custom_bond::{
+        BondInfo, BondInfoResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PriceGuard, QueryMsg,
+        Terms, VestingKind,
+    },
+    response::MsgInstantiateContractResponse,
 };
-use terraswap::asset::AssetInfo;
+use protobuf::Message;
+use terraswap::asset::{Asset, AssetInfo};
 
-pub fn instantiate_custom_bond(
+/// Instantiate msg every fixture starts from, tests override only the fields they need
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        custom_treasury: String::from("custom_treasury"),
+        principal_token: AssetInfo::Token {
+            contract_addr: String::from("principal_token"),
+        },
+        olympus_treasury: String::from("olympus_treasury"),
+        subsidy_router: String::from("subsidy_router"),
+        initial_owner: String::from("policy"),
+        olympus_dao: String::from("olympus_dao"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        principal_recipient: None,
+        note_nft_code_id: None,
+        pauser: None,
+        price_guard: None,
+        bonding_calculator: None,
+        terms_delay: None,
+        measure_received_balance: None,
+        terms: None,
+        initial_debt: None,
+        fee_recipient_admin: None,
+    }
+}
+
+/// Instantiates the bond with `msg` as `policy`
+pub fn instantiate_with_msg(
     deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
-    payout_decimals: Option<u8>,
-    principal_decimals: Option<u8>,
-) -> StdResult<InstantiateMsg> {
-    let payout_decimals = if let Some(decimals) = payout_decimals {
-        decimals
-    } else {
-        6u8
-    };
-    let principal_decimals = if let Some(decimals) = principal_decimals {
-        decimals
-    } else {
-        6u8
-    };
+    msg: InstantiateMsg,
+) -> Result<InstantiateMsg, ContractError> {
+    let info = mock_info("policy", &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info, msg.clone())?;
+
+    Ok(msg)
+}
+
+/// Mocks a cw20 payout token held by `custom_treasury` and the decimals of `principal_token`
+pub fn mock_payout_token(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    principal_token: &str,
+    principal_decimals: u8,
+    payout_decimals: u8,
+) {
     deps.querier.with_token_info(
         &[(
             &String::from("payout_token"),
             &Uint128::from(1000000000000u128),
         )],
         &[
-            (&String::from("principal_token"), &principal_decimals),
+            (&String::from(principal_token), &principal_decimals),
             (&String::from("payout_token"), &payout_decimals),
         ],
     );
     deps.querier.with_custom_treasury(
         String::from("custom_treasury"),
-        String::from("payout_token"),
+        AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
     );
+}
 
-    let msg = InstantiateMsg {
-        custom_treasury: String::from("custom_treasury"),
-        principal_token: AssetInfo::Token {
-            contract_addr: String::from("principal_token"),
-        },
-        olympus_treasury: String::from("olympus_treasury"),
-        subsidy_router: String::from("subsidy_router"),
-        initial_owner: String::from("policy"),
-        olympus_dao: String::from("olympus_dao"),
-        fee_tiers: vec![],
-        fee_in_payout: true,
+pub fn instantiate_custom_bond(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    payout_decimals: Option<u8>,
+    principal_decimals: Option<u8>,
+) -> Result<InstantiateMsg, ContractError> {
+    let payout_decimals = if let Some(decimals) = payout_decimals {
+        decimals
+    } else {
+        6u8
     };
+    let principal_decimals = if let Some(decimals) = principal_decimals {
+        decimals
+    } else {
+        6u8
+    };
+    mock_payout_token(deps, "principal_token", principal_decimals, payout_decimals);
 
-    let info = mock_info("policy", &[]);
-
-    instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
-
-    Ok(msg)
+    instantiate_with_msg(deps, instantiate_msg())
 }
 
 pub fn instantiate_custom_bond_with_principal_token(
     deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
     payout_decimals: Option<u8>,
     principal_token: AssetInfo,
-) -> StdResult<InstantiateMsg> {
+) -> Result<InstantiateMsg, ContractError> {
     let payout_decimals = if let Some(decimals) = payout_decimals {
         decimals
     } else {
@@ -81,27 +124,122 @@ pub fn instantiate_custom_bond_with_principal_token(
     );
     deps.querier.with_custom_treasury(
         String::from("custom_treasury"),
-        String::from("payout_token"),
+        AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
     );
 
-    let msg = InstantiateMsg {
-        custom_treasury: String::from("custom_treasury"),
-        principal_token,
-        olympus_treasury: String::from("olympus_treasury"),
-        subsidy_router: String::from("subsidy_router"),
-        initial_owner: String::from("policy"),
-        olympus_dao: String::from("olympus_dao"),
-        fee_tiers: vec![],
-        fee_in_payout: true,
-    };
+    instantiate_with_msg(
+        deps,
+        InstantiateMsg {
+            principal_token,
+            ..instantiate_msg()
+        },
+    )
+}
 
-    let info = mock_info("policy", &[]);
+pub fn instantiate_custom_bond_with_note_nft(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+) -> Result<InstantiateMsg, ContractError> {
+    mock_payout_token(deps, "principal_token", 6u8, 6u8);
+
+    let msg = instantiate_with_msg(
+        deps,
+        InstantiateMsg {
+            note_nft_code_id: Some(3u64),
+            ..instantiate_msg()
+        },
+    )?;
 
-    instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    let mut note_inst_res = MsgInstantiateContractResponse::new();
+    note_inst_res.set_contract_address("note_nft".to_string());
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(note_inst_res.write_to_bytes().unwrap().into()),
+        }),
+    };
+
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
 
     Ok(msg)
 }
 
+/// Bonds `principal_token` by the balance the contract gains on `DepositFrom`
+pub fn instantiate_custom_bond_measuring_received_balance(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+) -> Result<InstantiateMsg, ContractError> {
+    mock_payout_token(deps, "principal_token", 6u8, 6u8);
+
+    instantiate_with_msg(
+        deps,
+        InstantiateMsg {
+            measure_received_balance: Some(true),
+            ..instantiate_msg()
+        },
+    )
+}
+
+/// Pays out `uusd` from a treasury while the bank reports 500000 UST in supply
+pub fn instantiate_custom_bond_with_native_payout(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+) -> Result<InstantiateMsg, ContractError> {
+    deps.querier
+        .with_token_info(&[], &[(&String::from("principal_token"), &6u8)]);
+    deps.querier
+        .with_native_supplies(&[(&String::from("uusd"), &Uint128::from(500000000000u128))]);
+    deps.querier.with_custom_treasury(
+        String::from("custom_treasury"),
+        AssetInfo::NativeToken {
+            denom: String::from("uusd"),
+        },
+    );
+
+    instantiate_with_msg(deps, instantiate_msg())
+}
+
+/// Bonds `lp_token` of a pair holding 1000 UST and 250 payout tokens with 500 LP supply
+pub fn instantiate_custom_bond_with_lp_principal(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    liquidity_token: &str,
+) -> Result<InstantiateMsg, ContractError> {
+    mock_payout_token(deps, "lp_token", 6u8, 6u8);
+    deps.querier.with_pair_pool(
+        String::from("pair"),
+        [
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: String::from("uusd"),
+                },
+                amount: Uint128::from(1000000000u128),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: String::from("payout_token"),
+                },
+                amount: Uint128::from(250000000u128),
+            },
+        ],
+        Uint128::from(500000000u128),
+        String::from(liquidity_token),
+    );
+
+    instantiate_with_msg(
+        deps,
+        InstantiateMsg {
+            principal_token: AssetInfo::Token {
+                contract_addr: String::from("lp_token"),
+            },
+            bonding_calculator: Some(BondingCalculator::TerraswapLp {
+                pair: String::from("pair"),
+            }),
+            ..instantiate_msg()
+        },
+    )
+}
+
 pub fn initialize_bond(
     deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
     env: Env,
@@ -110,7 +248,9 @@ pub fn initialize_bond(
 
     let terms = Terms {
         control_variable: Decimal::from_ratio(1u128, 10u128),
+        vesting_kind: VestingKind::FixedTerm,
         vesting_term: 864000,
+        vesting_end: 0,
         minimum_price: Decimal::from_str("0.157284").unwrap(),
         max_payout: Decimal::from_ratio(2u128, 100000u128),
         max_debt: Uint128::from(300000u128),
@@ -126,32 +266,121 @@ pub fn initialize_bond(
     (terms, initial_debt)
 }
 
+pub fn initialize_fixed_expiry_bond(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+    vesting_end: u64,
+) -> (Terms, Uint128) {
+    let info = mock_info("policy", &[]);
+
+    let terms = Terms {
+        control_variable: Decimal::from_ratio(1u128, 10u128),
+        vesting_kind: VestingKind::FixedExpiry,
+        vesting_term: 0,
+        vesting_end,
+        minimum_price: Decimal::from_str("0.157284").unwrap(),
+        max_payout: Decimal::from_ratio(2u128, 100000u128),
+        max_debt: Uint128::from(300000u128),
+    };
+    let initial_debt = Uint128::from(12500u128);
+    let msg = ExecuteMsg::InitializeBond {
+        terms: terms.clone(),
+        initial_debt,
+    };
+
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    (terms, initial_debt)
+}
+
+/// Guards the bond with a pair holding 1000000 payout tokens against `principal_reserve`
+pub fn set_price_guard(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    min_discount: Decimal,
+    max_discount: Decimal,
+    principal_reserve: Uint128,
+) {
+    mock_price_guard_pair(deps, principal_reserve);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePriceGuard {
+        price_guard: Some(PriceGuard {
+            pair: String::from("pair"),
+            min_discount,
+            max_discount,
+        }),
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+pub fn mock_price_guard_pair(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    principal_reserve: Uint128,
+) {
+    deps.querier.with_pair_pool(
+        String::from("pair"),
+        [
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: String::from("payout_token"),
+                },
+                amount: Uint128::from(1000000u128),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: String::from("principal_token"),
+                },
+                amount: principal_reserve,
+            },
+        ],
+        Uint128::zero(),
+        String::from("lp_token"),
+    );
+}
+
+pub const TERMS_DELAY: u64 = 86400;
+
+pub fn set_terms_delay(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) {
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SetTermsDelay {
+        terms_delay: TERMS_DELAY,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
 pub fn deposit(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, env: Env) -> BondInfo {
     let amount = Uint128::from(100000u128);
     let info = mock_info("principal_token", &[]);
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "addr".to_string(),
+        sender: "depositor".to_string(),
         msg: to_binary(&Cw20HookMsg::Deposit {
             max_price: Decimal::from_str("0.17476").unwrap(),
             depositor: String::from("depositor"),
+            allow_partial: false,
+            min_payout: None,
+            deadline: None,
+            referrer: None,
         })
         .unwrap(),
         amount,
     });
 
-    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let note_id = res
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == "note_id")
+        .unwrap()
+        .value
+        .parse::<u64>()
+        .unwrap();
 
-    let res = query(
-        deps.as_ref(),
-        env.clone(),
-        QueryMsg::BondInfo {
-            user: String::from("depositor"),
-        },
-    )
-    .unwrap();
-    let bond_info: BondInfoResponse = from_binary(&res).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Note { note_id }).unwrap();
+    let note: BondInfoResponse = from_binary(&res).unwrap();
 
-    bond_info.info
+    note.info
 }
 
 pub fn increase_time(env: &mut Env, addition: u64) {
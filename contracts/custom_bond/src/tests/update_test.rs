@@ -1,17 +1,32 @@
-use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{attr, from_binary, Decimal, StdError, Uint128};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, BankMsg, Coin, CosmosMsg, Decimal, SubMsg, Uint128, WasmMsg,
+};
 use std::str::FromStr;
 
-use olympus_pro::custom_bond::{
-    Adjustment, ConfigResponse, ExecuteMsg, FeeTier, InstantiateMsg, QueryMsg, State, Terms,
+use cw20::Cw20ExecuteMsg;
+use olympus_pro::{
+    bonding_calculator::BondingCalculator,
+    custom_bond::{
+        Adjustment, ConfigResponse, CurrentOlympusFeeResponse, ExecuteMsg, FeeTier, InstantiateMsg,
+        PriceGuard, QueryMsg, State, Terms, VestingKind,
+    },
+    ownership::{OwnershipError, ProposalResponse},
+    utils::FeeTierError,
 };
-use terraswap::asset::AssetInfo;
+use terraswap::asset::{Asset, AssetInfo};
 
 use crate::{
     contract::{execute, instantiate, query},
+    error::ContractError,
     tests::{
         mock_querier::mock_dependencies,
-        test_utils::{initialize_bond, instantiate_custom_bond},
+        test_utils::{
+            deposit, increase_time, initialize_bond, instantiate_custom_bond,
+            instantiate_custom_bond_with_lp_principal,
+            instantiate_custom_bond_with_principal_token, instantiate_msg, instantiate_with_msg,
+            mock_payout_token, mock_price_guard_pair,
+        },
     },
 };
 
@@ -28,18 +43,12 @@ fn test_initialization() {
     );
     deps.querier.with_custom_treasury(
         String::from("custom_treasury"),
-        String::from("payout_token"),
+        AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
     );
 
     let msg = InstantiateMsg {
-        custom_treasury: String::from("custom_treasury"),
-        principal_token: AssetInfo::Token {
-            contract_addr: String::from("principal_token"),
-        },
-        olympus_treasury: String::from("olympus_treasury"),
-        subsidy_router: String::from("subsidy_router"),
-        initial_owner: String::from("policy"),
-        olympus_dao: String::from("olympus_dao"),
         fee_tiers: vec![
             FeeTier {
                 tier_ceiling: Uint128::from(100000u128),
@@ -50,7 +59,7 @@ fn test_initialization() {
                 fee_rate: Decimal::from_ratio(2u128, 1000u128),
             },
         ],
-        fee_in_payout: true,
+        ..instantiate_msg()
     };
 
     let info = mock_info("policy", &[]);
@@ -64,7 +73,9 @@ fn test_initialization() {
     assert_eq!(
         ConfigResponse {
             custom_treasury: String::from("custom_treasury"),
-            payout_token: String::from("payout_token"),
+            payout_token: AssetInfo::Token {
+                contract_addr: String::from("payout_token"),
+            },
             principal_token: AssetInfo::Token {
                 contract_addr: String::from("principal_token"),
             },
@@ -83,6 +94,16 @@ fn test_initialization() {
                 },
             ],
             fee_in_payout: true,
+            principal_recipient: String::from("custom_treasury"),
+            note_nft: None,
+            pauser: String::from("policy"),
+            price_guard: None,
+            bonding_calculator: BondingCalculator::Reserve {},
+            terms_delay: 0,
+            fee_recipients: vec![],
+            referrer_share: Decimal::zero(),
+            measure_received_balance: false,
+            fee_recipient_admin: None,
         },
         config
     );
@@ -93,36 +114,70 @@ fn test_initialization() {
 }
 
 #[test]
-fn test_update_policy_fails_if_unauthorized() {
+fn test_propose_new_policy_fails_if_unauthorized() {
     let mut deps = mock_dependencies(&[]);
 
     instantiate_custom_bond(&mut deps, None, None).unwrap();
 
     let info = mock_info("addr", &[]);
-    let msg = ExecuteMsg::UpdatePolicy {
+    let msg = ExecuteMsg::ProposeNewPolicy {
         policy: String::from("new_policy"),
+        expires: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
-fn test_update_policy_by_policy() {
+fn test_transfer_policy() {
     let mut deps = mock_dependencies(&[]);
 
     instantiate_custom_bond(&mut deps, None, None).unwrap();
 
     let info = mock_info("policy", &[]);
-    let msg = ExecuteMsg::UpdatePolicy {
+    let msg = ExecuteMsg::ProposeNewPolicy {
         policy: String::from("new_policy"),
+        expires: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     assert_eq!(
         res.attributes,
         vec![
-            attr("action", "update_policy"),
+            attr("action", "propose_new_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
+
+    // the policy only changes once the proposal is accepted
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(String::from("policy"), config.policy);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PolicyProposal {}).unwrap();
+    let proposal: Option<ProposalResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        Some(ProposalResponse {
+            proposed: String::from("new_policy"),
+            expires: None,
+        }),
+        proposal
+    );
+
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NotProposed {})
+    );
+
+    let info = mock_info("new_policy", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_policy"),
             attr("policy", "new_policy")
         ]
     );
@@ -130,6 +185,204 @@ fn test_update_policy_by_policy() {
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(String::from("new_policy"), config.policy);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PolicyProposal {}).unwrap();
+    let proposal: Option<ProposalResponse> = from_binary(&res).unwrap();
+    assert_eq!(None, proposal);
+
+    // the old policy lost its privileges
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePauser {
+        pauser: String::from("new_pauser"),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_accept_policy_fails_if_expired() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    let expires = env.block.time.seconds() + 100;
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: Some(env.block.time.seconds()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::InvalidExpiry {
+            expires: env.block.time.seconds()
+        })
+    );
+
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: Some(expires),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    increase_time(&mut env, 101);
+
+    let info = mock_info("new_policy", &[]);
+    let res = execute(deps.as_mut(), env, info, ExecuteMsg::AcceptPolicy {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::ProposalExpired { expires })
+    );
+}
+
+#[test]
+fn test_cancel_proposal() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::CancelProposal {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NoPendingProposal {})
+    );
+
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CancelProposal {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "cancel_proposal")]);
+
+    let info = mock_info("new_policy", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NoPendingProposal {})
+    );
+}
+
+#[test]
+fn test_transfer_olympus_dao() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    // the policy cannot move the olympus dao role
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::ProposeNewOlympusDao {
+        olympus_dao: String::from("new_olympus_dao"),
+        expires: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info("olympus_dao", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "propose_new_olympus_dao"),
+            attr("olympus_dao", "new_olympus_dao")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::OlympusDaoProposal {}).unwrap();
+    let proposal: Option<ProposalResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        Some(ProposalResponse {
+            proposed: String::from("new_olympus_dao"),
+            expires: None,
+        }),
+        proposal
+    );
+
+    // the policy proposal is stored separately
+    let info = mock_info("new_olympus_dao", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::AcceptPolicy {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NoPendingProposal {})
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::AcceptOlympusDao {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_olympus_dao"),
+            attr("olympus_dao", "new_olympus_dao")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(String::from("new_olympus_dao"), config.olympus_dao);
+}
+
+#[test]
+fn test_cancel_olympus_dao_proposal_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("olympus_dao", &[]);
+    let msg = ExecuteMsg::ProposeNewOlympusDao {
+        olympus_dao: String::from("new_olympus_dao"),
+        expires: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CancelOlympusDaoProposal {},
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info("olympus_dao", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CancelOlympusDaoProposal {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "cancel_olympus_dao_proposal")]
+    );
 }
 
 #[test]
@@ -144,7 +397,7 @@ fn test_update_olympus_treasury_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -175,6 +428,165 @@ fn test_update_olympus_treasury_by_olympus_dao() {
     );
 }
 
+#[test]
+fn test_update_olympus_treasury_by_fee_recipient_admin() {
+    let mut deps = mock_dependencies(&[]);
+
+    mock_payout_token(&mut deps, "principal_token", 6u8, 6u8);
+    instantiate_with_msg(
+        &mut deps,
+        InstantiateMsg {
+            fee_recipient_admin: Some(String::from("factory")),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    let info = mock_info("factory", &[]);
+    let msg = ExecuteMsg::UpdateOlympusTreasury {
+        olympus_treasury: String::from("partner_treasury"),
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(String::from("partner_treasury"), config.olympus_treasury);
+    assert_eq!(Some(String::from("factory")), config.fee_recipient_admin);
+}
+
+#[test]
+fn test_initialization_fails_with_invalid_fee_tiers() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_custom_bond(&mut deps, None, None).unwrap();
+    let info = mock_info("policy", &[]);
+
+    msg.fee_tiers = vec![
+        FeeTier {
+            tier_ceiling: Uint128::from(200000u128),
+            fee_rate: Decimal::permille(1),
+        },
+        FeeTier {
+            tier_ceiling: Uint128::from(100000u128),
+            fee_rate: Decimal::permille(2),
+        },
+    ];
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::FeeTier(FeeTierError::UnorderedCeilings {
+            previous: Uint128::from(200000u128),
+            ceiling: Uint128::from(100000u128),
+        })
+    );
+
+    msg.fee_tiers = vec![FeeTier {
+        tier_ceiling: Uint128::from(100000u128),
+        fee_rate: Decimal::one(),
+    }];
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::FeeTier(FeeTierError::FeeRateTooHigh {
+            fee_rate: Decimal::one(),
+        })
+    );
+}
+
+#[test]
+fn test_update_fee_tiers_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdateFeeTiers { fee_tiers: vec![] };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_update_fee_tiers_by_olympus_dao() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let mut env = mock_env();
+    initialize_bond(&mut deps, env.clone());
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::CurrentOlympusFee {}).unwrap();
+    let fee: CurrentOlympusFeeResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        CurrentOlympusFeeResponse {
+            fee_rate: Decimal::zero(),
+            tier: None,
+        },
+        fee
+    );
+
+    let fee_tiers = vec![
+        FeeTier {
+            tier_ceiling: Uint128::from(100000u128),
+            fee_rate: Decimal::percent(1),
+        },
+        FeeTier {
+            tier_ceiling: Uint128::from(200000u128),
+            fee_rate: Decimal::percent(2),
+        },
+    ];
+
+    let info = mock_info("olympus_dao", &[]);
+    let msg = ExecuteMsg::UpdateFeeTiers {
+        fee_tiers: vec![FeeTier {
+            tier_ceiling: Uint128::from(100000u128),
+            fee_rate: Decimal::percent(100),
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::FeeTier(FeeTierError::FeeRateTooHigh {
+            fee_rate: Decimal::percent(100),
+        })
+    );
+
+    let msg = ExecuteMsg::UpdateFeeTiers {
+        fee_tiers: fee_tiers.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "update_fee_tiers")]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(fee_tiers, config.fee_tiers);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::CurrentOlympusFee {}).unwrap();
+    let fee: CurrentOlympusFeeResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        CurrentOlympusFeeResponse {
+            fee_rate: Decimal::percent(1),
+            tier: Some(0),
+        },
+        fee
+    );
+
+    // 100000 principal bonded moves the bond into the second tier
+    increase_time(&mut env, 100);
+    deposit(&mut deps, env.clone());
+
+    let res = query(deps.as_ref(), env, QueryMsg::CurrentOlympusFee {}).unwrap();
+    let fee: CurrentOlympusFeeResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        CurrentOlympusFeeResponse {
+            fee_rate: Decimal::percent(2),
+            tier: Some(1),
+        },
+        fee
+    );
+}
+
 #[test]
 fn test_initialize_bond_fails_if_unauthorized() {
     let mut deps = mock_dependencies(&[]);
@@ -185,7 +597,9 @@ fn test_initialize_bond_fails_if_unauthorized() {
     let msg = ExecuteMsg::InitializeBond {
         terms: Terms {
             control_variable: Decimal::from_ratio(1u128, 10u128),
+            vesting_kind: VestingKind::FixedTerm,
             vesting_term: 864000,
+            vesting_end: 0,
             minimum_price: Decimal::from_str("0.157284").unwrap(),
             max_payout: Decimal::from_ratio(1u128, 10000u128),
             max_debt: Uint128::from(1000000u128),
@@ -194,7 +608,7 @@ fn test_initialize_bond_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -207,7 +621,9 @@ fn test_initialize_bond_by_policy() {
     let msg = ExecuteMsg::InitializeBond {
         terms: Terms {
             control_variable: Decimal::from_ratio(1u128, 10u128),
+            vesting_kind: VestingKind::FixedTerm,
             vesting_term: 864000,
+            vesting_end: 0,
             minimum_price: Decimal::from_str("0.157284").unwrap(),
             max_payout: Decimal::from_ratio(1u128, 10000u128),
             max_debt: Uint128::from(1000000u128),
@@ -226,7 +642,9 @@ fn test_initialize_bond_by_policy() {
             total_debt: Uint128::from(100000u128),
             terms: Terms {
                 control_variable: Decimal::from_ratio(1u128, 10u128),
+                vesting_kind: VestingKind::FixedTerm,
                 vesting_term: 864000,
+                vesting_end: 0,
                 minimum_price: Decimal::from_str("0.157284").unwrap(),
                 max_payout: Decimal::from_ratio(1u128, 10000u128),
                 max_debt: Uint128::from(1000000u128),
@@ -236,6 +654,7 @@ fn test_initialize_bond_by_policy() {
             payout_since_last_subsidy: Uint128::zero(),
             total_principal_bonded: Uint128::zero(),
             total_payout_given: Uint128::zero(),
+            paused: false,
         },
         state
     );
@@ -253,7 +672,9 @@ fn test_initialize_bond_fails_if_current_debt_is_not_zero() {
     let msg = ExecuteMsg::InitializeBond {
         terms: Terms {
             control_variable: Decimal::from_ratio(1u128, 10u128),
+            vesting_kind: VestingKind::FixedTerm,
             vesting_term: 864000,
+            vesting_end: 0,
             minimum_price: Decimal::from_str("0.157284").unwrap(),
             max_payout: Decimal::from_ratio(1u128, 10000u128),
             max_debt: Uint128::from(1000000u128),
@@ -262,10 +683,7 @@ fn test_initialize_bond_fails_if_current_debt_is_not_zero() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(
-        res,
-        StdError::generic_err("debt must be 0 for initialization")
-    );
+    assert_eq!(res, ContractError::DebtNotZero {});
 }
 
 #[test]
@@ -278,7 +696,9 @@ fn test_initialize_bond_fails_if_vesting_term_is_less_than_36hours() {
     let msg = ExecuteMsg::InitializeBond {
         terms: Terms {
             control_variable: Decimal::from_ratio(1u128, 10u128),
+            vesting_kind: VestingKind::FixedTerm,
             vesting_term: 86400,
+            vesting_end: 0,
             minimum_price: Decimal::from_str("0.157284").unwrap(),
             max_payout: Decimal::from_ratio(1u128, 10000u128),
             max_debt: Uint128::from(1000000u128),
@@ -287,10 +707,32 @@ fn test_initialize_bond_fails_if_vesting_term_is_less_than_36hours() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(
-        res,
-        StdError::generic_err("vesting must be longer than 36 hours")
-    );
+    assert_eq!(res, ContractError::VestingTooShort {});
+}
+
+#[test]
+fn test_initialize_bond_fails_if_vesting_end_is_less_than_36hours_away() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let env = mock_env();
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::InitializeBond {
+        terms: Terms {
+            control_variable: Decimal::from_ratio(1u128, 10u128),
+            vesting_kind: VestingKind::FixedExpiry,
+            vesting_term: 0,
+            vesting_end: env.block.time.seconds() + 86400,
+            minimum_price: Decimal::from_str("0.157284").unwrap(),
+            max_payout: Decimal::from_ratio(1u128, 10000u128),
+            max_debt: Uint128::from(1000000u128),
+        },
+        initial_debt: Uint128::from(100000u128),
+    };
+
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::VestingTooShort {});
 }
 
 #[test]
@@ -303,7 +745,9 @@ fn test_initialize_bond_fails_if_max_payout_is_greater_or_euqal_than_1percent()
     let msg = ExecuteMsg::InitializeBond {
         terms: Terms {
             control_variable: Decimal::from_ratio(1u128, 10u128),
+            vesting_kind: VestingKind::FixedTerm,
             vesting_term: 864000,
+            vesting_end: 0,
             minimum_price: Decimal::from_str("0.157284").unwrap(),
             max_payout: Decimal::percent(2),
             max_debt: Uint128::from(1000000u128),
@@ -312,10 +756,55 @@ fn test_initialize_bond_fails_if_max_payout_is_greater_or_euqal_than_1percent()
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(
-        res,
-        StdError::generic_err("payout cannot be above 1 percent")
-    );
+    assert_eq!(res, ContractError::MaxPayoutTooHigh {});
+}
+
+#[test]
+fn test_instantiate_with_terms_initializes_bond() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_custom_bond(&mut deps, None, None).unwrap();
+    let terms = Terms {
+        control_variable: Decimal::from_ratio(1u128, 10u128),
+        vesting_kind: VestingKind::FixedTerm,
+        vesting_term: 864000,
+        vesting_end: 0,
+        minimum_price: Decimal::from_str("0.157284").unwrap(),
+        max_payout: Decimal::from_ratio(1u128, 10000u128),
+        max_debt: Uint128::from(1000000u128),
+    };
+    msg.terms = Some(terms.clone());
+    msg.initial_debt = Some(Uint128::from(100000u128));
+
+    let env = mock_env();
+    let info = mock_info("factory", &[]);
+    instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap();
+    let state: State = from_binary(&res).unwrap();
+    assert_eq!(terms, state.terms);
+    assert_eq!(Uint128::from(100000u128), state.total_debt);
+    assert_eq!(env.block.time.seconds(), state.last_decay);
+}
+
+#[test]
+fn test_instantiate_with_terms_fails_if_max_payout_is_greater_or_equal_than_1percent() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_custom_bond(&mut deps, None, None).unwrap();
+    msg.terms = Some(Terms {
+        control_variable: Decimal::from_ratio(1u128, 10u128),
+        vesting_kind: VestingKind::FixedTerm,
+        vesting_term: 864000,
+        vesting_end: 0,
+        minimum_price: Decimal::from_str("0.157284").unwrap(),
+        max_payout: Decimal::percent(2),
+        max_debt: Uint128::from(1000000u128),
+    });
+
+    let info = mock_info("factory", &[]);
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::MaxPayoutTooHigh {});
 }
 
 #[test]
@@ -327,12 +816,13 @@ fn test_set_bond_terms_fails_if_unauthorized() {
     let info = mock_info("addr", &[]);
     let msg = ExecuteMsg::SetBondTerms {
         vesting_term: Some(864000u64),
+        vesting_end: None,
         max_payout: Some(Decimal::from_ratio(1u128, 10000u128)),
         max_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -344,6 +834,7 @@ fn test_send_bond_terms_by_policy_set_vesting_term() {
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::SetBondTerms {
         vesting_term: Some(864000u64),
+        vesting_end: None,
         max_payout: None,
         max_debt: None,
     };
@@ -366,15 +857,13 @@ fn test_send_bond_terms_fails_if_vesting_term_is_less_than_36hours() {
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::SetBondTerms {
         vesting_term: Some(86400u64),
+        vesting_end: None,
         max_payout: None,
         max_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(
-        res,
-        StdError::generic_err("vesting must be longer than 36 hours")
-    );
+    assert_eq!(res, ContractError::VestingTooShort {});
 }
 
 #[test]
@@ -386,6 +875,7 @@ fn test_send_bond_terms_by_policy_set_max_payout() {
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::SetBondTerms {
         vesting_term: None,
+        vesting_end: None,
         max_payout: Some(Decimal::from_ratio(1u128, 1000u128)),
         max_debt: None,
     };
@@ -408,15 +898,13 @@ fn test_send_bond_terms_fails_if_max_payout_is_greater_or_equal_than_1percent()
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::SetBondTerms {
         vesting_term: None,
+        vesting_end: None,
         max_payout: Some(Decimal::percent(2)),
         max_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(
-        res,
-        StdError::generic_err("payout cannot be above 1 percent")
-    );
+    assert_eq!(res, ContractError::MaxPayoutTooHigh {});
 }
 
 #[test]
@@ -428,6 +916,7 @@ fn test_send_bond_terms_by_policy_set_max_debt() {
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::SetBondTerms {
         vesting_term: None,
+        vesting_end: None,
         max_payout: None,
         max_debt: Some(Uint128::from(10000u128)),
     };
@@ -458,7 +947,7 @@ fn test_set_adjustment_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -513,5 +1002,325 @@ fn test_set_adjustment_fails_if_increment_is_greater_than_30percent_of_control_v
 
     let env = mock_env();
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("increment too large"));
+    assert_eq!(
+        res,
+        ContractError::IncrementTooLarge {
+            increment: Decimal::from_str("0.004").unwrap(),
+            max: Decimal::from_str("0.003").unwrap(),
+        }
+    );
+}
+
+#[test]
+fn test_approve_and_revoke_depositor() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("user", &[]);
+    let msg = ExecuteMsg::ApproveDepositor {
+        operator: Some(String::from("operator")),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "approve_depositor"),
+            attr("user", "user"),
+            attr("operator", "operator"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::DepositorApproval {
+            user: String::from("user"),
+            operator: String::from("operator"),
+        },
+    )
+    .unwrap();
+    let approved: bool = from_binary(&res).unwrap();
+    assert!(approved);
+
+    let info = mock_info("user", &[]);
+    let msg = ExecuteMsg::RevokeDepositor {
+        operator: Some(String::from("operator")),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "revoke_depositor"),
+            attr("user", "user"),
+            attr("operator", "operator"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::DepositorApproval {
+            user: String::from("user"),
+            operator: String::from("operator"),
+        },
+    )
+    .unwrap();
+    let approved: bool = from_binary(&res).unwrap();
+    assert!(!approved);
+}
+
+#[test]
+fn test_sweep_principal_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("addr", &[]);
+    let msg = ExecuteMsg::SweepPrincipal {};
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_sweep_principal_fails_if_nothing_to_sweep() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SweepPrincipal {};
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::NothingToSweep {});
+}
+
+#[test]
+fn test_sweep_cw20_principal_by_policy() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &String::from("principal_token"),
+        &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::from(1000u128))],
+    )]);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SweepPrincipal {};
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "sweep_principal"),
+            attr("amount", "1000"),
+            attr("recipient", "custom_treasury"),
+        ]
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("principal_token"),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("custom_treasury"),
+                amount: Uint128::from(1000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+}
+
+#[test]
+fn test_sweep_native_principal_by_policy() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000u128),
+    }]);
+
+    instantiate_custom_bond_with_principal_token(
+        &mut deps,
+        None,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    )
+    .unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::SweepPrincipal {};
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("custom_treasury"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        }))]
+    );
+}
+
+#[test]
+fn test_update_price_guard_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("addr", &[]);
+    let msg = ExecuteMsg::UpdatePriceGuard { price_guard: None };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_update_price_guard_fails_if_discount_band_is_invalid() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePriceGuard {
+        price_guard: Some(PriceGuard {
+            pair: String::from("pair"),
+            min_discount: Decimal::percent(30),
+            max_discount: Decimal::percent(10),
+        }),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidPriceGuardDiscount {});
+
+    let msg = ExecuteMsg::UpdatePriceGuard {
+        price_guard: Some(PriceGuard {
+            pair: String::from("pair"),
+            min_discount: Decimal::percent(10),
+            max_discount: Decimal::one(),
+        }),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidPriceGuardDiscount {});
+}
+
+#[test]
+fn test_update_price_guard_fails_if_pair_does_not_trade_principal() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+
+    deps.querier.with_pair_pool(
+        String::from("pair"),
+        [
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: String::from("payout_token"),
+                },
+                amount: Uint128::from(1000000u128),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: String::from("uusd"),
+                },
+                amount: Uint128::from(1000000u128),
+            },
+        ],
+        Uint128::zero(),
+        String::from("lp_token"),
+    );
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePriceGuard {
+        price_guard: Some(PriceGuard {
+            pair: String::from("pair"),
+            min_discount: Decimal::percent(5),
+            max_discount: Decimal::percent(30),
+        }),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidPriceGuardPair {});
+}
+
+#[test]
+fn test_update_price_guard_fails_with_lp_bonding_calculator() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_lp_principal(&mut deps, "lp_token").unwrap();
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePriceGuard {
+        price_guard: Some(PriceGuard {
+            pair: String::from("pair"),
+            min_discount: Decimal::percent(5),
+            max_discount: Decimal::percent(30),
+        }),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::PriceGuardWithLpCalculator {});
+}
+
+#[test]
+fn test_update_price_guard_by_policy() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond(&mut deps, None, None).unwrap();
+    mock_price_guard_pair(&mut deps, Uint128::from(200000u128));
+
+    let price_guard = PriceGuard {
+        pair: String::from("pair"),
+        min_discount: Decimal::percent(5),
+        max_discount: Decimal::percent(30),
+    };
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdatePriceGuard {
+        price_guard: Some(price_guard.clone()),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "update_price_guard")]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(Some(price_guard), config.price_guard);
+
+    let msg = ExecuteMsg::UpdatePriceGuard { price_guard: None };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(None, config.price_guard);
+}
+
+#[test]
+fn test_instantiate_with_lp_bonding_calculator() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_bond_with_lp_principal(&mut deps, "lp_token").unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        BondingCalculator::TerraswapLp {
+            pair: String::from("pair"),
+        },
+        config.bonding_calculator
+    );
+}
+
+#[test]
+fn test_instantiate_fails_if_principal_is_not_the_pair_liquidity_token() {
+    let mut deps = mock_dependencies(&[]);
+
+    let res = instantiate_custom_bond_with_lp_principal(&mut deps, "other_lp_token").unwrap_err();
+    assert_eq!(res, ContractError::PrincipalNotLiquidityToken {});
 }
@@ -1,20 +1,39 @@
 use cosmwasm_bignumber::Decimal256;
-use cosmwasm_std::{Decimal, Deps, Fraction, MessageInfo, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{
+    Addr, Api, BankMsg, CanonicalAddr, CosmosMsg, Decimal, Deps, Fraction, MessageInfo,
+    QuerierWrapper, StdResult, Storage, Uint128,
+};
 use olympus_pro::{
-    custom_bond::{BondInfo, FeeTier, State},
+    bonding_calculator::BondingCalculator,
+    custom_bond::{BondInfo, DepositFailure, FeeRecipient, PriceGuard, State, Terms, VestingKind},
+    querier::{query_pair_info, query_pair_pool, query_supply},
     utils::get_value_of_token,
 };
-use terraswap::asset::{Asset, AssetInfoRaw};
+use terraswap::asset::{Asset, AssetInfo, AssetInfoRaw};
 
-use crate::state::{read_config, Config};
+use crate::error::ContractError;
+use crate::state::{
+    read_config, read_depositor_approval, read_open_deposit, Config, FeeRecipientRaw, PriceGuardRaw,
+};
 
 fn get_debt_decay(state: State, current_time: u64) -> Uint128 {
     let time_since_last = current_time - state.last_decay;
-    if time_since_last > state.terms.vesting_term {
+    let decay_period = match state.terms.vesting_kind {
+        VestingKind::FixedTerm => state.terms.vesting_term,
+        VestingKind::FixedExpiry => state.terms.vesting_end.saturating_sub(state.last_decay),
+    };
+    if time_since_last >= decay_period {
         state.total_debt
     } else {
-        state.total_debt
-            * Decimal::from_ratio(time_since_last as u128, state.terms.vesting_term as u128)
+        state.total_debt * Decimal::from_ratio(time_since_last as u128, decay_period as u128)
+    }
+}
+
+/// Seconds a note deposited at `current_time` vests for
+pub fn get_vesting(terms: &Terms, current_time: u64) -> u64 {
+    match terms.vesting_kind {
+        VestingKind::FixedTerm => terms.vesting_term,
+        VestingKind::FixedExpiry => terms.vesting_end.saturating_sub(current_time),
     }
 }
 
@@ -33,21 +52,19 @@ pub fn decay_debt(state: &mut State, current_time: u64) {
     state.last_decay = current_time;
 }
 
-pub fn get_current_olympus_fee(config: Config, state: State) -> Decimal {
-    for fee_tier in config.fee_tiers.clone() {
-        if state.total_principal_bonded < fee_tier.tier_ceiling {
-            return fee_tier.fee_rate;
-        }
-    }
-
+/// Index of the first tier whose ceiling is above the principal bonded so far, else the last one
+pub fn get_current_fee_tier(config: &Config, state: &State) -> Option<usize> {
     config
         .fee_tiers
-        .last()
-        .unwrap_or(&FeeTier {
-            tier_ceiling: Uint128::zero(),
-            fee_rate: Decimal::zero(),
-        })
-        .fee_rate
+        .iter()
+        .position(|fee_tier| state.total_principal_bonded < fee_tier.tier_ceiling)
+        .or_else(|| config.fee_tiers.len().checked_sub(1))
+}
+
+pub fn get_current_olympus_fee(config: Config, state: State) -> Decimal {
+    get_current_fee_tier(&config, &state)
+        .map(|tier| config.fee_tiers[tier].fee_rate)
+        .unwrap_or_else(Decimal::zero)
 }
 
 pub fn get_debt_ratio(state: State, payout_total_supply: Uint128, current_time: u64) -> Decimal {
@@ -77,6 +94,22 @@ pub fn get_true_bond_price(
     )
 }
 
+pub fn get_value_of_principal(
+    deps: Deps,
+    config: &Config,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    Ok(config.bonding_calculator.to_normal(deps.api)?.valuation(
+        &deps.querier,
+        Asset {
+            info: config.principal_token.to_normal(deps.api)?,
+            amount,
+        },
+        config.payout_decimals,
+        config.principal_decimals,
+    )?)
+}
+
 pub fn get_payout_for(
     deps: Deps,
     config: Config,
@@ -84,7 +117,7 @@ pub fn get_payout_for(
     value: Uint128,
     payout_total_supply: Uint128,
     current_time: u64,
-) -> StdResult<(Uint128, Uint128)> {
+) -> Result<(Uint128, Uint128), ContractError> {
     let current_olympus_fee = get_current_olympus_fee(config.clone(), state.clone());
 
     let bond_price = get_bond_price(state.clone(), payout_total_supply, current_time);
@@ -95,14 +128,8 @@ pub fn get_payout_for(
         Ok((total.checked_sub(fee)?, fee))
     } else {
         let fee = value * current_olympus_fee;
-        let payout = get_value_of_token(
-            Asset {
-                info: config.principal_token.to_normal(deps.api)?,
-                amount: value.checked_sub(fee)?,
-            },
-            config.payout_decimals,
-            config.principal_decimals,
-        ) * bond_price.inv().unwrap();
+        let payout = get_value_of_principal(deps, &config, value.checked_sub(fee)?)?
+            * bond_price.inv().unwrap();
         Ok((payout, fee))
     }
 }
@@ -135,11 +162,369 @@ pub fn adjust(state: &mut State, current_time: u64) -> StdResult<(bool, Decimal)
     }
 }
 
-pub fn get_received_native_fund(storage: &dyn Storage, info: MessageInfo) -> StdResult<Uint128> {
+/// The guard pair must trade the payout against the principal, which an LP principal never is
+pub fn price_guard_to_raw(
+    deps: Deps,
+    price_guard: Option<PriceGuard>,
+    payout_token: &AssetInfo,
+    principal_token: &AssetInfo,
+    bonding_calculator: &BondingCalculator,
+) -> Result<Option<PriceGuardRaw>, ContractError> {
+    if let Some(price_guard) = price_guard {
+        if price_guard.min_discount > price_guard.max_discount
+            || price_guard.max_discount >= Decimal::one()
+        {
+            return Err(ContractError::InvalidPriceGuardDiscount {});
+        }
+        if let BondingCalculator::TerraswapLp { .. } = bonding_calculator {
+            return Err(ContractError::PriceGuardWithLpCalculator {});
+        }
+
+        let pair_info = query_pair_info(&deps.querier, price_guard.pair.clone())?;
+        if !pair_info.asset_infos.contains(payout_token)
+            || !pair_info.asset_infos.contains(principal_token)
+        {
+            return Err(ContractError::InvalidPriceGuardPair {});
+        }
+
+        Ok(Some(PriceGuardRaw {
+            pair: deps.api.addr_canonicalize(&price_guard.pair)?,
+            min_discount: price_guard.min_discount,
+            max_discount: price_guard.max_discount,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Weights must be positive and sum within a u64, which `split_fee` relies on
+pub fn fee_recipients_to_raw(
+    api: &dyn Api,
+    fee_recipients: Vec<FeeRecipient>,
+) -> Result<Vec<FeeRecipientRaw>, ContractError> {
+    let mut total_weight: u64 = 0;
+    fee_recipients
+        .into_iter()
+        .map(|fee_recipient| {
+            if fee_recipient.weight == 0 {
+                return Err(ContractError::InvalidFeeRecipientWeight {});
+            }
+            total_weight = total_weight
+                .checked_add(fee_recipient.weight)
+                .ok_or(ContractError::FeeRecipientWeightOverflow {})?;
+
+            Ok(FeeRecipientRaw {
+                address: api.addr_canonicalize(&fee_recipient.address)?,
+                weight: fee_recipient.weight,
+            })
+        })
+        .collect()
+}
+
+/// Returns the referrer share of `fee` and how the rest is split between the fee recipients,
+/// rounding dust going to the first one
+pub fn split_fee(
+    api: &dyn Api,
+    config: &Config,
+    fee: Uint128,
+    has_referrer: bool,
+) -> StdResult<(Uint128, Vec<(Addr, Uint128)>)> {
+    let referrer_fee = if has_referrer {
+        fee * config.referrer_share
+    } else {
+        Uint128::zero()
+    };
+    let remaining = fee.checked_sub(referrer_fee)?;
+
+    if config.fee_recipients.is_empty() {
+        return Ok((
+            referrer_fee,
+            vec![(api.addr_humanize(&config.olympus_treasury)?, remaining)],
+        ));
+    }
+
+    let total_weight: u64 = config
+        .fee_recipients
+        .iter()
+        .map(|fee_recipient| fee_recipient.weight)
+        .sum();
+    let mut shares = config
+        .fee_recipients
+        .iter()
+        .map(|fee_recipient| {
+            Ok((
+                api.addr_humanize(&fee_recipient.address)?,
+                remaining.multiply_ratio(fee_recipient.weight, total_weight),
+            ))
+        })
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+
+    let distributed: Uint128 = shares.iter().map(|(_, amount)| *amount).sum();
+    shares[0].1 += remaining.checked_sub(distributed)?;
+
+    Ok((referrer_fee, shares))
+}
+
+/// Builds the transfer of `asset` to `recipient` along with the amount that arrives,
+/// native transfers paying the Terra tax out of `asset.amount`
+pub fn into_msg_after_tax(
+    querier: &QuerierWrapper,
+    asset: Asset,
+    recipient: Addr,
+) -> StdResult<(CosmosMsg, Uint128)> {
+    match asset.info {
+        AssetInfo::NativeToken { .. } => {
+            let coin = asset.deduct_tax(querier)?;
+            let received = coin.amount;
+            Ok((
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![coin],
+                }),
+                received,
+            ))
+        }
+        AssetInfo::Token { .. } => {
+            let received = asset.amount;
+            Ok((asset.into_msg(querier, recipient)?, received))
+        }
+    }
+}
+
+/// Returns why the guard rejects `bond_price` against the spot price of the guard pair, if it does
+pub fn check_price_guard(
+    deps: Deps,
+    config: &Config,
+    price_guard: &PriceGuardRaw,
+    bond_price: Decimal,
+) -> StdResult<Option<DepositFailure>> {
+    let pool = query_pair_pool(
+        &deps.querier,
+        deps.api.addr_humanize(&price_guard.pair)?.to_string(),
+    )?;
+
+    let payout_token = config.payout_token.to_normal(deps.api)?;
+    let principal_token = config.principal_token.to_normal(deps.api)?;
+
+    let payout_reserve = pool.assets.iter().find(|asset| asset.info == payout_token);
+    let principal_reserve = pool
+        .assets
+        .iter()
+        .find(|asset| asset.info == principal_token);
+
+    let (payout_reserve, principal_reserve) = match (payout_reserve, principal_reserve) {
+        (Some(payout_reserve), Some(principal_reserve)) => {
+            (payout_reserve.amount, principal_reserve.clone())
+        }
+        _ => return Ok(Some(DepositFailure::InvalidPriceGuardPair {})),
+    };
+    if payout_reserve.is_zero() {
+        return Ok(Some(DepositFailure::PriceGuardNoLiquidity {}));
+    }
+
+    let market_price = Decimal::from_ratio(
+        get_value_of_token(
+            principal_reserve,
+            config.payout_decimals,
+            config.principal_decimals,
+        ),
+        payout_reserve,
+    );
+
+    if bond_price > market_price {
+        return Ok(Some(DepositFailure::BondPriceAboveMarket {
+            bond_price,
+            market_price,
+        }));
+    }
+    let max_price = decimal_multiplication_in_256(
+        market_price,
+        decimal_subtraction_in_256(Decimal::one(), price_guard.min_discount),
+    );
+    if bond_price > max_price {
+        return Ok(Some(DepositFailure::DiscountBelowMinimum {
+            bond_price,
+            max_price,
+        }));
+    }
+    let min_price = decimal_multiplication_in_256(
+        market_price,
+        decimal_subtraction_in_256(Decimal::one(), price_guard.max_discount),
+    );
+    if bond_price < min_price {
+        return Ok(Some(DepositFailure::DiscountAboveMaximum {
+            bond_price,
+            min_price,
+        }));
+    }
+
+    Ok(None)
+}
+
+pub struct DepositQuote {
+    pub value: Uint128,
+    pub payout: Uint128,
+    pub fee: Uint128,
+    pub true_price: Decimal,
+    pub vesting: u64,
+    pub payout_total_supply: Uint128,
+    pub failures: Vec<DepositFailure>,
+}
+
+/// Decays `state` and adds a deposit of `amount` principal to its debt, collecting every check
+/// the deposit fails in order. Shared by `deposit` and the `SimulateDeposit` query.
+pub fn quote_deposit(
+    deps: Deps,
+    config: &Config,
+    state: &mut State,
+    amount: Uint128,
+    max_price: Option<Decimal>,
+    current_time: u64,
+) -> Result<DepositQuote, ContractError> {
+    let mut failures: Vec<DepositFailure> = vec![];
+
+    if amount.is_zero() {
+        failures.push(DepositFailure::AmountIsZero {});
+    }
+    if state.paused {
+        failures.push(DepositFailure::DepositsPaused {});
+    }
+
+    let vesting = get_vesting(&state.terms, current_time);
+    if vesting == 0 {
+        failures.push(DepositFailure::BondExpired {});
+    }
+
+    decay_debt(state, current_time);
+
+    let payout_total_supply =
+        query_supply(&deps.querier, &config.payout_token.to_normal(deps.api)?)?;
+
+    let true_price = get_true_bond_price(
+        config.clone(),
+        state.clone(),
+        payout_total_supply,
+        current_time,
+    );
+
+    if let Some(max_price) = max_price {
+        if max_price < true_price {
+            failures.push(DepositFailure::MaxPriceExceeded {
+                price: true_price,
+                max_price,
+            });
+        }
+    }
+
+    if let Some(price_guard) = config.price_guard.as_ref() {
+        if let Some(failure) = check_price_guard(deps, config, price_guard, true_price)? {
+            failures.push(failure);
+        }
+    }
+
+    let value = get_value_of_principal(deps, config, amount)?;
+
+    let (payout, fee) = get_payout_for(
+        deps,
+        config.clone(),
+        state.clone(),
+        if config.fee_in_payout { value } else { amount },
+        payout_total_supply,
+        current_time,
+    )?;
+
+    let min_payout = Uint128::from(
+        10u128
+            .checked_pow((config.payout_decimals - 2) as u32)
+            .unwrap(),
+    );
+    let max_payout = get_max_payout(state.clone(), payout_total_supply)?;
+    if payout < min_payout {
+        failures.push(DepositFailure::BondTooSmall {
+            payout,
+            min: min_payout,
+        });
+    } else if payout > max_payout {
+        failures.push(DepositFailure::BondTooLarge {
+            payout,
+            max: max_payout,
+        });
+    }
+
+    state.total_debt += value;
+    if state.total_debt > state.terms.max_debt {
+        failures.push(DepositFailure::MaxCapacityReached {
+            debt: state.total_debt,
+            max_debt: state.terms.max_debt,
+        });
+    }
+
+    Ok(DepositQuote {
+        value,
+        payout,
+        fee,
+        true_price,
+        vesting,
+        payout_total_supply,
+        failures,
+    })
+}
+
+/// Like `quote_deposit`, but scales `amount` down to the largest part that fits the max payout and
+/// the debt capacity. Returns the filled amount with its quote.
+pub fn quote_partial_deposit(
+    deps: Deps,
+    config: &Config,
+    state: &mut State,
+    amount: Uint128,
+    max_price: Option<Decimal>,
+    current_time: u64,
+) -> Result<(Uint128, DepositQuote), ContractError> {
+    let initial_state = state.clone();
+    let mut filled = amount;
+    loop {
+        *state = initial_state.clone();
+        let quote = quote_deposit(deps, config, state, filled, max_price, current_time)?;
+        if quote.failures.is_empty()
+            || !quote.failures.iter().all(|failure| {
+                matches!(
+                    failure,
+                    DepositFailure::BondTooLarge { .. } | DepositFailure::MaxCapacityReached { .. }
+                )
+            })
+        {
+            return Ok((filled, quote));
+        }
+
+        let mut fill = filled;
+        let max_payout = get_max_payout(state.clone(), quote.payout_total_supply)?;
+        if quote.payout > max_payout {
+            fill = std::cmp::min(fill, filled.multiply_ratio(max_payout, quote.payout));
+        }
+        if state.total_debt > state.terms.max_debt {
+            let capacity = (state.terms.max_debt + quote.value).saturating_sub(state.total_debt);
+            fill = std::cmp::min(fill, filled.multiply_ratio(capacity, quote.value));
+        }
+        // rounding can leave the scaled amount just over the limit
+        if fill >= filled {
+            fill = filled.checked_sub(Uint128::from(1u128))?;
+        }
+
+        if fill.is_zero() {
+            return Ok((filled, quote));
+        }
+        filled = fill;
+    }
+}
+
+pub fn get_received_native_fund(
+    storage: &dyn Storage,
+    info: MessageInfo,
+) -> Result<Uint128, ContractError> {
     let config = read_config(storage)?;
 
     if info.funds.len() != 1u64 as usize {
-        return Err(StdError::generic_err("invalid denom received"));
+        return Err(ContractError::InvalidDenom {});
     }
     if let AssetInfoRaw::NativeToken { denom } = config.principal_token {
         let amount: Uint128 = info
@@ -150,21 +535,31 @@ pub fn get_received_native_fund(storage: &dyn Storage, info: MessageInfo) -> Std
             .unwrap_or_else(Uint128::zero);
         Ok(amount)
     } else {
-        Err(StdError::generic_err("not support cw20 token"))
+        Err(ContractError::NotSupportCw20 {})
     }
 }
 
+pub fn is_depositor_approved(
+    storage: &dyn Storage,
+    user: &CanonicalAddr,
+    operator: &CanonicalAddr,
+) -> bool {
+    user == operator
+        || read_open_deposit(storage, user).unwrap_or_default()
+        || read_depositor_approval(storage, user, operator).unwrap_or_default()
+}
+
+/// `bond_info.vesting` counts from `last_time` for both fixed-term and fixed-expiry notes
 pub fn get_pending_payout(bond_info: BondInfo, time_since_last: u64) -> Uint128 {
-    let mut payout = bond_info.payout
+    if time_since_last >= bond_info.vesting {
+        return bond_info.payout;
+    }
+
+    bond_info.payout
         * Decimal::from_ratio(
             Uint128::from(time_since_last as u128),
             Uint128::from(bond_info.vesting as u128),
-        );
-    if payout > bond_info.payout {
-        payout = bond_info.payout;
-    }
-
-    payout
+        )
 }
 
 pub fn decimal_multiplication_in_256(a: Decimal, b: Decimal) -> Decimal {
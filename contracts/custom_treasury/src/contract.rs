@@ -2,13 +2,17 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    attr, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Uint128,
+    attr, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Response, StdResult, Uint128,
 };
 
-use olympus_pro::custom_treasury::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use olympus_pro::{
+    custom_treasury::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    ownership::{accept, cancel, propose, query_proposal, KEY_POLICY_PROPOSAL},
+};
 use terraswap::asset::{Asset, AssetInfo};
 
+use crate::error::ContractError;
 use crate::query::{query_bond_whitelist, query_config};
 use crate::state::{read_bond_whitelist, read_config, store_bond_whitelist, store_config, Config};
 
@@ -18,11 +22,11 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     store_config(
         deps.storage,
         &Config {
-            payout_token: deps.api.addr_canonicalize(&msg.payout_token)?,
+            payout_token: msg.payout_token.to_raw(deps.api)?,
             policy: deps.api.addr_canonicalize(&msg.initial_owner)?,
         },
     )?;
@@ -33,16 +37,20 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::SendPayoutTokens { amount } => send_payout_token(deps, info, amount),
+        ExecuteMsg::AcceptPolicy {} => accept_policy(deps, env, info),
         _ => {
             assert_policy_privilege(deps.as_ref(), info)?;
             match msg {
-                ExecuteMsg::UpdateConfig { policy } => update_config(deps, policy),
+                ExecuteMsg::ProposeNewPolicy { policy, expires } => {
+                    propose_new_policy(deps, env, policy, expires)
+                }
+                ExecuteMsg::CancelProposal {} => cancel_proposal(deps),
                 ExecuteMsg::Withdraw { asset, recipient } => withdraw(deps, asset, recipient),
                 ExecuteMsg::WhitelistBond { bond, whitelist } => {
                     whitelist_bond(deps, bond, whitelist)
@@ -58,35 +66,65 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::BondWhitelist { bond } => to_binary(&query_bond_whitelist(deps, bond)?),
+        QueryMsg::PolicyProposal {} => to_binary(&query_proposal(deps, KEY_POLICY_PROPOSAL)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     Ok(Response::default())
 }
 
-fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> StdResult<()> {
+fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> Result<(), ContractError> {
     if read_config(deps.storage)?.policy != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     Ok(())
 }
 
-fn update_config(deps: DepsMut, policy: Option<String>) -> StdResult<Response> {
-    let mut config = read_config(deps.storage)?;
+fn propose_new_policy(
+    deps: DepsMut,
+    env: Env,
+    policy: String,
+    expires: Option<u64>,
+) -> Result<Response, ContractError> {
+    propose(deps, &env, KEY_POLICY_PROPOSAL, &policy, expires)?;
 
-    if let Some(policy) = policy {
-        config.policy = deps.api.addr_canonicalize(&policy)?;
-    }
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_policy"),
+        attr("policy", policy),
+    ]))
+}
+
+fn accept_policy(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let policy = accept(deps.branch(), &env, KEY_POLICY_PROPOSAL, &info.sender)?;
 
+    let mut config = read_config(deps.storage)?;
+    config.policy = policy;
     store_config(deps.storage, &config)?;
 
-    Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_policy"),
+        attr("policy", info.sender),
+    ]))
+}
+
+fn cancel_proposal(deps: DepsMut) -> Result<Response, ContractError> {
+    cancel(deps.storage, KEY_POLICY_PROPOSAL)?;
+
+    Ok(Response::new().add_attribute("action", "cancel_proposal"))
 }
 
-fn send_payout_token(deps: DepsMut, info: MessageInfo, amount: Uint128) -> StdResult<Response> {
+fn send_payout_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
     let whitelist = read_bond_whitelist(
         deps.storage,
         &deps.api.addr_canonicalize(info.sender.as_str())?,
@@ -96,26 +134,32 @@ fn send_payout_token(deps: DepsMut, info: MessageInfo, amount: Uint128) -> StdRe
     if whitelist {
         let config = read_config(deps.storage)?;
 
-        let asset = Asset {
-            info: AssetInfo::Token {
-                contract_addr: deps.api.addr_humanize(&config.payout_token)?.to_string(),
-            },
-            amount: amount,
+        // the bond must receive the full amount it pays out, so native tax is paid on top
+        let msg = match config.payout_token.to_normal(deps.api)? {
+            AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin { denom, amount }],
+            }),
+            payout_token => Asset {
+                info: payout_token,
+                amount,
+            }
+            .into_msg(&deps.querier, info.sender.clone())?,
         };
 
-        Ok(Response::new()
-            .add_message(asset.clone().into_msg(&deps.querier, info.sender.clone())?)
-            .add_attributes(vec![
-                attr("action", "send_payout_token"),
-                attr("amount", amount),
-                attr("recipient", info.sender.to_string()),
-            ]))
+        Ok(Response::new().add_message(msg).add_attributes(vec![
+            attr("action", "send_payout_token"),
+            attr("amount", amount),
+            attr("recipient", info.sender.to_string()),
+        ]))
     } else {
-        Err(StdError::generic_err("not whitelisted"))
+        Err(ContractError::NotWhitelisted {
+            bond: info.sender.to_string(),
+        })
     }
 }
 
-fn withdraw(deps: DepsMut, asset: Asset, recipient: String) -> StdResult<Response> {
+fn withdraw(deps: DepsMut, asset: Asset, recipient: String) -> Result<Response, ContractError> {
     Ok(Response::new()
         .add_message(
             asset
@@ -129,7 +173,7 @@ fn withdraw(deps: DepsMut, asset: Asset, recipient: String) -> StdResult<Respons
         ]))
 }
 
-fn whitelist_bond(deps: DepsMut, bond: String, whitelist: bool) -> StdResult<Response> {
+fn whitelist_bond(deps: DepsMut, bond: String, whitelist: bool) -> Result<Response, ContractError> {
     store_bond_whitelist(
         deps.storage,
         &deps.api.addr_canonicalize(&bond)?,
@@ -0,0 +1,18 @@
+use cosmwasm_std::StdError;
+use olympus_pro::ownership::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("not whitelisted: {bond}")]
+    NotWhitelisted { bond: String },
+}
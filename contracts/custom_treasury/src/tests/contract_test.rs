@@ -1,15 +1,22 @@
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, BankMsg, Coin, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, BankMsg, Coin, CosmosMsg, Decimal, SubMsg, Uint128, WasmMsg,
 };
 
 use cw20::Cw20ExecuteMsg;
-use olympus_pro::custom_treasury::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use olympus_pro::{
+    custom_treasury::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+    ownership::{OwnershipError, ProposalResponse},
+};
 use terraswap::asset::{Asset, AssetInfo};
 
 use crate::{
     contract::{execute, instantiate, query},
-    tests::{mock_querier::mock_dependencies, test_utils::instantiate_custom_treasury},
+    error::ContractError,
+    tests::{
+        mock_querier::mock_dependencies,
+        test_utils::{instantiate_custom_treasury, instantiate_custom_treasury_with_payout_token},
+    },
 };
 
 #[test]
@@ -17,7 +24,9 @@ fn test_initialization() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        payout_token: String::from("payout_token"),
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
         initial_owner: String::from("policy"),
     };
 
@@ -31,7 +40,9 @@ fn test_initialization() {
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
         ConfigResponse {
-            payout_token: String::from("payout_token"),
+            payout_token: AssetInfo::Token {
+                contract_addr: String::from("payout_token"),
+            },
             policy: String::from("policy"),
         },
         config
@@ -39,39 +50,76 @@ fn test_initialization() {
 }
 
 #[test]
-fn test_update_config_fails_if_unauthorized() {
+fn test_propose_new_policy_fails_if_unauthorized() {
     let mut deps = mock_dependencies(&[]);
 
     instantiate_custom_treasury(&mut deps);
 
     let info = mock_info("addr", &[]);
-    let msg = ExecuteMsg::UpdateConfig {
-        policy: Some(String::from("new_policy")),
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
-fn test_update_config_by_policy() {
+fn test_transfer_policy() {
     let mut deps = mock_dependencies(&[]);
 
     instantiate_custom_treasury(&mut deps);
 
     let info = mock_info("policy", &[]);
-    let msg = ExecuteMsg::UpdateConfig {
-        policy: Some(String::from("new_policy")),
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_eq!(res.attributes, vec![attr("action", "update_config"),]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "propose_new_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PolicyProposal {}).unwrap();
+    let proposal: Option<ProposalResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        Some(ProposalResponse {
+            proposed: String::from("new_policy"),
+            expires: None,
+        }),
+        proposal
+    );
+
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NotProposed {})
+    );
+
+    let info = mock_info("new_policy", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
         ConfigResponse {
-            payout_token: String::from("payout_token"),
+            payout_token: AssetInfo::Token {
+                contract_addr: String::from("payout_token"),
+            },
             policy: String::from("new_policy"),
         },
         config
@@ -91,7 +139,7 @@ fn test_whitelist_bond_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -188,7 +236,7 @@ fn test_withdraw_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -281,7 +329,12 @@ fn test_send_payout_tokens_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("not whitelisted"));
+    assert_eq!(
+        res,
+        ContractError::NotWhitelisted {
+            bond: String::from("addr")
+        }
+    );
 }
 
 #[test]
@@ -326,6 +379,93 @@ fn test_send_payout_tokens_by_bond() {
     );
 }
 
+#[test]
+fn test_send_native_payout_tokens_by_bond() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_custom_treasury_with_payout_token(
+        &mut deps,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        config.payout_token
+    );
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::WhitelistBond {
+        bond: String::from("bond"),
+        whitelist: true,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("bond", &[]);
+    let msg = ExecuteMsg::SendPayoutTokens {
+        amount: Uint128::from(100000000u128),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("bond"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(100000000u128)
+            }],
+        }))]
+    );
+}
+
+#[test]
+fn test_send_native_payout_tokens_pays_tax_on_top() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    instantiate_custom_treasury_with_payout_token(
+        &mut deps,
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    );
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::WhitelistBond {
+        bond: String::from("bond"),
+        whitelist: true,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("bond", &[]);
+    let msg = ExecuteMsg::SendPayoutTokens {
+        amount: Uint128::from(100000000u128),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("bond"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(100000000u128)
+            }],
+        }))]
+    );
+}
+
 // #[test]
 // fn test_query_value_of_token_when_same_decimals() {
 //     let mut deps = mock_dependencies(&[]);
@@ -2,12 +2,25 @@ use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
 use cosmwasm_std::OwnedDeps;
 
 use olympus_pro::custom_treasury::InstantiateMsg;
+use terraswap::asset::AssetInfo;
 
 use crate::{contract::instantiate, tests::mock_querier::WasmMockQuerier};
 
 pub fn instantiate_custom_treasury(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) {
+    instantiate_custom_treasury_with_payout_token(
+        deps,
+        AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
+    );
+}
+
+pub fn instantiate_custom_treasury_with_payout_token(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    payout_token: AssetInfo,
+) {
     let msg = InstantiateMsg {
-        payout_token: String::from("payout_token"),
+        payout_token,
         initial_owner: String::from("policy"),
     };
 
@@ -2,23 +2,41 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError,
-    StdResult, SubMsg, WasmMsg,
+    attr, to_binary, Binary, CanonicalAddr, ContractResult, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, SubMsgExecutionResponse, Uint128,
+    WasmMsg,
 };
 
 use olympus_pro::{
-    custom_bond::{FeeTier, InstantiateMsg as CustomBondInstantiateMsg},
-    custom_treasury::InstantiateMsg as CustomTreasuryInstantiateMsg,
-    factory::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    custom_bond::{
+        ExecuteMsg as CustomBondExecuteMsg, FeeTier, InstantiateMsg as CustomBondInstantiateMsg,
+        Terms,
+    },
+    custom_treasury::{
+        ExecuteMsg as CustomTreasuryExecuteMsg, InstantiateMsg as CustomTreasuryInstantiateMsg,
+    },
+    factory::{CreationStatus, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    ownership::{accept, cancel, propose, query_proposal, KEY_POLICY_PROPOSAL},
+    response::MsgInstantiateContractResponse,
+    subsidy_router::{
+        ConfigResponse as SubsidyRouterConfigResponse, ExecuteMsg as SubsidyRouterExecuteMsg,
+        QueryMsg as SubsidyRouterQueryMsg,
+    },
+    utils::validate_fee_tiers,
 };
 use protobuf::Message;
 use terraswap::asset::AssetInfo;
 
-use crate::query::{query_bond_info, query_config, query_state};
-use crate::response::MsgInstantiateContractResponse;
+use crate::error::ContractError;
+use crate::query::{
+    query_bond_info, query_bonds, query_bonds_by_custom_treasury, query_bonds_by_initial_owner,
+    query_bonds_by_principal_token, query_config, query_fee_recipient, query_pending_creations,
+    query_state,
+};
 use crate::state::{
-    read_config, read_temp_bond_info, remove_temp_bond_info, store_config, store_new_bond_info,
-    store_state, store_temp_bond_info, BondInfo, Config, State, TempBondInfo,
+    read_bond_info, read_config, read_pending_bond_info, remove_pending_bond_info, store_bond_info,
+    store_config, store_new_bond_info, store_new_pending_bond_info, store_pending_bond_info,
+    store_state, BondInfo, Config, PendingBondInfo, State,
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -27,7 +45,7 @@ pub fn instantiate(
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     store_config(
         deps.storage,
         &Config {
@@ -40,100 +58,184 @@ pub fn instantiate(
         },
     )?;
 
-    store_state(deps.storage, &State { bond_length: 0 })?;
+    store_state(
+        deps.storage,
+        &State {
+            bond_length: 0,
+            creation_length: 0,
+        },
+    )?;
 
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
-    assert_policy_privilege(deps.as_ref(), info)?;
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateConfig {
-            custom_bond_id,
-            custom_treasury_id,
-            policy,
-        } => update_config(deps, custom_bond_id, custom_treasury_id, policy),
-        ExecuteMsg::CreateBondAndTreasury {
-            payout_token,
-            principal_token,
-            initial_owner,
-            fee_tiers,
-            fee_in_payout,
-        } => create_bond_and_treasury(
-            deps,
-            env,
-            payout_token,
-            principal_token,
-            initial_owner,
-            fee_tiers,
-            fee_in_payout,
-        ),
-        ExecuteMsg::CreateBond {
-            principal_token,
-            custom_treasury,
-            initial_owner,
-            fee_tiers,
-            fee_in_payout,
-        } => create_bond(
-            deps,
-            env,
-            principal_token,
-            custom_treasury,
-            initial_owner,
-            fee_tiers,
-            fee_in_payout,
-        ),
+        ExecuteMsg::AcceptPolicy {} => accept_policy(deps, env, info),
+        ExecuteMsg::UpdateBondFeeRecipient {
+            bond_id,
+            fee_recipient,
+        } => update_bond_fee_recipient(deps, info, bond_id, fee_recipient),
+        _ => {
+            assert_policy_privilege(deps.as_ref(), info)?;
+            match msg {
+                ExecuteMsg::UpdateConfig {
+                    custom_bond_id,
+                    custom_treasury_id,
+                } => update_config(deps, custom_bond_id, custom_treasury_id),
+                ExecuteMsg::ProposeNewPolicy { policy, expires } => {
+                    propose_new_policy(deps, env, policy, expires)
+                }
+                ExecuteMsg::CancelProposal {} => cancel_proposal(deps),
+                ExecuteMsg::CreateBondAndTreasury {
+                    payout_token,
+                    principal_token,
+                    initial_owner,
+                    fee_tiers,
+                    fee_in_payout,
+                    subsidy_controller,
+                    terms,
+                    initial_debt,
+                } => create_bond_and_treasury(
+                    deps,
+                    env,
+                    payout_token,
+                    principal_token,
+                    initial_owner,
+                    fee_tiers,
+                    fee_in_payout,
+                    subsidy_controller,
+                    terms,
+                    initial_debt,
+                ),
+                ExecuteMsg::CreateBond {
+                    principal_token,
+                    custom_treasury,
+                    initial_owner,
+                    fee_tiers,
+                    fee_in_payout,
+                    terms,
+                    initial_debt,
+                } => create_bond(
+                    deps,
+                    env,
+                    principal_token,
+                    custom_treasury,
+                    initial_owner,
+                    fee_tiers,
+                    fee_in_payout,
+                    terms,
+                    initial_debt,
+                ),
+                ExecuteMsg::ClearFailedCreation { creation_id } => {
+                    clear_failed_creation(deps, creation_id)
+                }
+                _ => panic!("do not enter here"),
+            }
+        }
     }
 }
 
-/// This just stores the result for future query
+/// Moves a bond creation forward, the reply id being its creation id. A failed submessage
+/// leaves the creation queryable as failed instead of reverting, until the policy clears it
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
-    match msg.id {
-        1 => {
-            let res: MsgInstantiateContractResponse = Message::parse_from_bytes(
-                msg.result.unwrap().data.unwrap().as_slice(),
-            )
-            .map_err(|_| {
-                StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
-            })?;
-            let treasury_addr = res.get_contract_address();
-
-            create_bond_from_temp(deps, env, treasury_addr.to_string())
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let creation_id = msg.id;
+    let pending_bond_info = read_pending_bond_info(deps.storage, creation_id)?
+        .ok_or(ContractError::InvalidReplyId { id: creation_id })?;
+
+    let res = match msg.result {
+        ContractResult::Ok(res) => res,
+        ContractResult::Err(error) => {
+            return fail_creation(deps, creation_id, pending_bond_info, error)
         }
-        2 => {
-            let res: MsgInstantiateContractResponse = Message::parse_from_bytes(
-                msg.result.unwrap().data.unwrap().as_slice(),
-            )
-            .map_err(|_| {
-                StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
-            })?;
-            let bond_addr = res.get_contract_address();
-
-            register_bond(deps, bond_addr.to_string())
+    };
+    let contract_addr = parse_instantiated_address(res)?;
+
+    match pending_bond_info.status {
+        CreationStatus::PendingTreasury => {
+            create_bond_from_pending(deps, env, creation_id, pending_bond_info, contract_addr)
+        }
+        CreationStatus::PendingBond => {
+            register_bond(deps, creation_id, pending_bond_info, contract_addr)
         }
-        _ => Err(StdError::generic_err("invalid reply id")),
+        CreationStatus::Failed => Err(ContractError::InvalidReplyId { id: creation_id }),
     }
 }
 
+fn parse_instantiated_address(res: SubMsgExecutionResponse) -> Result<String, ContractError> {
+    let data = res.data.ok_or(ContractError::MissingInstantiateData {})?;
+    let res: MsgInstantiateContractResponse = Message::parse_from_bytes(data.as_slice())
+        .map_err(|_| ContractError::InvalidInstantiateData {})?;
+
+    if res.get_contract_address().is_empty() {
+        return Err(ContractError::InvalidInstantiateData {});
+    }
+
+    Ok(res.get_contract_address().to_string())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::State {} => to_binary(&query_state(deps)?),
         QueryMsg::BondInfo { bond_id } => to_binary(&query_bond_info(deps, bond_id)?),
+        QueryMsg::Bonds { start_after, limit } => {
+            to_binary(&query_bonds(deps, start_after, limit)?)
+        }
+        QueryMsg::BondsByPrincipalToken {
+            principal_token,
+            start_after,
+            limit,
+        } => to_binary(&query_bonds_by_principal_token(
+            deps,
+            principal_token,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::BondsByCustomTreasury {
+            custom_treasury,
+            start_after,
+            limit,
+        } => to_binary(&query_bonds_by_custom_treasury(
+            deps,
+            custom_treasury,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::BondsByInitialOwner {
+            initial_owner,
+            start_after,
+            limit,
+        } => to_binary(&query_bonds_by_initial_owner(
+            deps,
+            initial_owner,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::FeeRecipient { bond_id } => to_binary(&query_fee_recipient(deps, bond_id)?),
+        QueryMsg::PendingCreations { start_after, limit } => {
+            to_binary(&query_pending_creations(deps, start_after, limit)?)
+        }
+        QueryMsg::PolicyProposal {} => to_binary(&query_proposal(deps, KEY_POLICY_PROPOSAL)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     Ok(Response::default())
 }
 
-fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> StdResult<()> {
+fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> Result<(), ContractError> {
     if read_config(deps.storage)?.policy != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     Ok(())
@@ -143,8 +245,7 @@ fn update_config(
     deps: DepsMut,
     custom_bond_id: Option<u64>,
     custom_treasury_id: Option<u64>,
-    policy: Option<String>,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config = read_config(deps.storage)?;
 
     if let Some(custom_bond_id) = custom_bond_id {
@@ -155,95 +256,200 @@ fn update_config(
         config.custom_treasury_id = custom_treasury_id;
     }
 
-    if let Some(policy) = policy {
-        config.policy = deps.api.addr_canonicalize(&policy)?;
-    }
-
     store_config(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
 }
 
+fn propose_new_policy(
+    deps: DepsMut,
+    env: Env,
+    policy: String,
+    expires: Option<u64>,
+) -> Result<Response, ContractError> {
+    propose(deps, &env, KEY_POLICY_PROPOSAL, &policy, expires)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_policy"),
+        attr("policy", policy),
+    ]))
+}
+
+fn accept_policy(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let policy = accept(deps.branch(), &env, KEY_POLICY_PROPOSAL, &info.sender)?;
+
+    let mut config = read_config(deps.storage)?;
+    config.policy = policy;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_policy"),
+        attr("policy", info.sender),
+    ]))
+}
+
+fn update_bond_fee_recipient(
+    deps: DepsMut,
+    info: MessageInfo,
+    bond_id: u64,
+    fee_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+    if config.olympus_dao != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::OnlyOlympusDao {});
+    }
+
+    let mut bond_info = read_bond_info(deps.storage, bond_id)?;
+    bond_info.fee_recipient = match &fee_recipient {
+        Some(fee_recipient) => Some(deps.api.addr_canonicalize(fee_recipient)?),
+        None => None,
+    };
+    store_bond_info(deps.storage, bond_id, &bond_info)?;
+
+    let fee_recipient = match fee_recipient {
+        Some(fee_recipient) => fee_recipient,
+        None => deps.api.addr_humanize(&config.treasury)?.to_string(),
+    };
+
+    // the factory is the fee recipient admin of the bonds it creates
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&bond_info.bond)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&CustomBondExecuteMsg::UpdateOlympusTreasury {
+                olympus_treasury: fee_recipient.clone(),
+            })?,
+        })
+        .add_attributes(vec![
+            attr("action", "update_bond_fee_recipient"),
+            attr("bond_id", bond_id.to_string()),
+            attr("fee_recipient", fee_recipient),
+        ]))
+}
+
+fn cancel_proposal(deps: DepsMut) -> Result<Response, ContractError> {
+    cancel(deps.storage, KEY_POLICY_PROPOSAL)?;
+
+    Ok(Response::new().add_attribute("action", "cancel_proposal"))
+}
+
+fn clear_failed_creation(deps: DepsMut, creation_id: u64) -> Result<Response, ContractError> {
+    match read_pending_bond_info(deps.storage, creation_id)? {
+        Some(pending_bond_info) if pending_bond_info.status == CreationStatus::Failed => {
+            remove_pending_bond_info(deps.storage, creation_id)
+        }
+        _ => return Err(ContractError::CreationNotFailed { creation_id }),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "clear_failed_creation"),
+        attr("creation_id", creation_id.to_string()),
+    ]))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn create_bond_and_treasury(
     deps: DepsMut,
     env: Env,
-    payout_token: String,
+    payout_token: AssetInfo,
     principal_token: AssetInfo,
     initial_owner: String,
     fee_tiers: Vec<FeeTier>,
     fee_in_payout: bool,
-) -> StdResult<Response> {
+    subsidy_controller: Option<String>,
+    terms: Option<Terms>,
+    initial_debt: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    validate_fee_tiers(&fee_tiers)?;
+
     let config = read_config(deps.storage)?;
 
-    store_temp_bond_info(
+    // checked up front, a failing registration would revert the whole creation
+    if subsidy_controller.is_some() {
+        let subsidy_router_config: SubsidyRouterConfigResponse = deps.querier.query_wasm_smart(
+            deps.api.addr_humanize(&config.subsidy_router)?,
+            &SubsidyRouterQueryMsg::Config {},
+        )?;
+        if subsidy_router_config.policy != env.contract.address {
+            return Err(ContractError::NotSubsidyRouterPolicy {});
+        }
+    }
+
+    let creation_id = store_new_pending_bond_info(
         deps.storage,
-        &TempBondInfo {
+        &PendingBondInfo {
             principal_token: principal_token.to_raw(deps.api)?,
             custom_treasury: None,
             initial_owner: deps.api.addr_canonicalize(&initial_owner)?,
-            fee_tiers: fee_tiers.clone(),
+            fee_tiers,
             fee_in_payout,
+            created_treasury: true,
+            subsidy_controller: match subsidy_controller {
+                Some(subsidy_controller) => Some(deps.api.addr_canonicalize(&subsidy_controller)?),
+                None => None,
+            },
+            terms,
+            initial_debt,
+            status: CreationStatus::PendingTreasury,
+            error: None,
         },
     )?;
 
     Ok(Response::new()
-        .add_attributes(vec![("action", "create_treasury")])
+        .add_attributes(vec![
+            attr("action", "create_treasury"),
+            attr("creation_id", creation_id.to_string()),
+        ])
         .add_submessage(SubMsg {
-            id: 1,
+            id: creation_id,
             gas_limit: None,
             msg: WasmMsg::Instantiate {
                 code_id: config.custom_treasury_id,
                 funds: vec![],
                 admin: Some(env.contract.address.to_string()),
                 label: "OlympusPro Custom Treasury".to_string(),
+                // the factory keeps the policy until the bond is whitelisted
                 msg: to_binary(&CustomTreasuryInstantiateMsg {
                     payout_token,
-                    initial_owner,
+                    initial_owner: env.contract.address.to_string(),
                 })?,
             }
             .into(),
-            reply_on: ReplyOn::Success,
+            reply_on: ReplyOn::Always,
         }))
 }
 
-fn create_bond_from_temp(deps: DepsMut, env: Env, custom_treasury: String) -> StdResult<Response> {
-    let mut temp_bond_info = read_temp_bond_info(deps.storage)?;
-
-    temp_bond_info.custom_treasury = Some(deps.api.addr_canonicalize(&custom_treasury)?);
-
-    store_temp_bond_info(deps.storage, &temp_bond_info)?;
+fn create_bond_from_pending(
+    deps: DepsMut,
+    env: Env,
+    creation_id: u64,
+    mut pending_bond_info: PendingBondInfo,
+    custom_treasury: String,
+) -> Result<Response, ContractError> {
+    pending_bond_info.custom_treasury = Some(deps.api.addr_canonicalize(&custom_treasury)?);
+    pending_bond_info.status = CreationStatus::PendingBond;
 
-    let config = read_config(deps.storage)?;
+    store_pending_bond_info(deps.storage, creation_id, &pending_bond_info)?;
 
     Ok(Response::new()
-        .add_attributes(vec![("action", "create_bond")])
-        .add_submessage(SubMsg {
-            id: 2,
-            gas_limit: None,
-            msg: WasmMsg::Instantiate {
-                code_id: config.custom_bond_id,
-                funds: vec![],
-                admin: Some(env.contract.address.to_string()),
-                label: "OlympusPro Custom Bond".to_string(),
-                msg: to_binary(&CustomBondInstantiateMsg {
-                    custom_treasury: custom_treasury.clone(),
-                    principal_token: temp_bond_info.principal_token.to_normal(deps.api)?,
-                    olympus_treasury: custom_treasury,
-                    subsidy_router: deps.api.addr_humanize(&config.subsidy_router)?.to_string(),
-                    initial_owner: deps
-                        .api
-                        .addr_humanize(&temp_bond_info.initial_owner)?
-                        .to_string(),
-                    olympus_dao: deps.api.addr_humanize(&config.olympus_dao)?.to_string(),
-                    fee_tiers: temp_bond_info.fee_tiers,
-                    fee_in_payout: temp_bond_info.fee_in_payout,
-                })?,
-            }
-            .into(),
-            reply_on: ReplyOn::Success,
-        }))
+        .add_attributes(vec![
+            attr("action", "create_bond"),
+            attr("creation_id", creation_id.to_string()),
+        ])
+        .add_submessage(instantiate_bond(
+            deps.as_ref(),
+            &env,
+            creation_id,
+            custom_treasury,
+            pending_bond_info,
+        )?))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn create_bond(
     deps: DepsMut,
     env: Env,
@@ -252,61 +458,193 @@ fn create_bond(
     initial_owner: String,
     fee_tiers: Vec<FeeTier>,
     fee_in_payout: bool,
-) -> StdResult<Response> {
+    terms: Option<Terms>,
+    initial_debt: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    validate_fee_tiers(&fee_tiers)?;
+
+    let pending_bond_info = PendingBondInfo {
+        principal_token: principal_token.to_raw(deps.api)?,
+        custom_treasury: Some(deps.api.addr_canonicalize(&custom_treasury)?),
+        initial_owner: deps.api.addr_canonicalize(&initial_owner)?,
+        fee_tiers,
+        fee_in_payout,
+        created_treasury: false,
+        subsidy_controller: None,
+        terms,
+        initial_debt,
+        status: CreationStatus::PendingBond,
+        error: None,
+    };
+    let creation_id = store_new_pending_bond_info(deps.storage, &pending_bond_info)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "create_bond"),
+            attr("creation_id", creation_id.to_string()),
+        ])
+        .add_submessage(instantiate_bond(
+            deps.as_ref(),
+            &env,
+            creation_id,
+            custom_treasury,
+            pending_bond_info,
+        )?))
+}
+
+fn instantiate_bond(
+    deps: Deps,
+    env: &Env,
+    creation_id: u64,
+    custom_treasury: String,
+    pending_bond_info: PendingBondInfo,
+) -> StdResult<SubMsg> {
     let config = read_config(deps.storage)?;
 
-    store_temp_bond_info(
-        deps.storage,
-        &TempBondInfo {
-            principal_token: principal_token.to_raw(deps.api)?,
-            custom_treasury: Some(deps.api.addr_canonicalize(&custom_treasury)?),
-            initial_owner: deps.api.addr_canonicalize(&initial_owner)?,
-            fee_tiers: fee_tiers.clone(),
-            fee_in_payout,
-        },
-    )?;
+    Ok(SubMsg {
+        id: creation_id,
+        gas_limit: None,
+        msg: WasmMsg::Instantiate {
+            code_id: config.custom_bond_id,
+            funds: vec![],
+            admin: Some(env.contract.address.to_string()),
+            label: "OlympusPro Custom Bond".to_string(),
+            msg: to_binary(&CustomBondInstantiateMsg {
+                custom_treasury,
+                principal_token: pending_bond_info.principal_token.to_normal(deps.api)?,
+                olympus_treasury: deps.api.addr_humanize(&config.treasury)?.to_string(),
+                subsidy_router: deps.api.addr_humanize(&config.subsidy_router)?.to_string(),
+                initial_owner: deps
+                    .api
+                    .addr_humanize(&pending_bond_info.initial_owner)?
+                    .to_string(),
+                olympus_dao: deps.api.addr_humanize(&config.olympus_dao)?.to_string(),
+                fee_tiers: pending_bond_info.fee_tiers,
+                fee_in_payout: pending_bond_info.fee_in_payout,
+                principal_recipient: None,
+                note_nft_code_id: None,
+                pauser: None,
+                price_guard: None,
+                bonding_calculator: None,
+                terms_delay: None,
+                measure_received_balance: None,
+                terms: pending_bond_info.terms,
+                initial_debt: pending_bond_info.initial_debt,
+                fee_recipient_admin: Some(env.contract.address.to_string()),
+            })?,
+        }
+        .into(),
+        reply_on: ReplyOn::Always,
+    })
+}
 
-    Ok(Response::new()
-        .add_attributes(vec![("action", "create_bond")])
-        .add_submessage(SubMsg {
-            id: 2,
-            gas_limit: None,
-            msg: WasmMsg::Instantiate {
-                code_id: config.custom_bond_id,
+/// Registers the bond, then wires it to a treasury created by the factory and to its subsidy
+/// controller
+fn register_bond(
+    deps: DepsMut,
+    creation_id: u64,
+    pending_bond_info: PendingBondInfo,
+    bond: String,
+) -> Result<Response, ContractError> {
+    let custom_treasury = pending_bond_info
+        .custom_treasury
+        .ok_or(ContractError::InvalidReplyId { id: creation_id })?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if pending_bond_info.created_treasury {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: deps.api.addr_humanize(&custom_treasury)?.to_string(),
                 funds: vec![],
-                admin: Some(env.contract.address.to_string()),
-                label: "OlympusPro Custom Bond".to_string(),
-                msg: to_binary(&CustomBondInstantiateMsg {
-                    custom_treasury: custom_treasury.clone(),
-                    principal_token,
-                    olympus_treasury: custom_treasury,
-                    subsidy_router: deps.api.addr_humanize(&config.subsidy_router)?.to_string(),
-                    initial_owner,
-                    olympus_dao: deps.api.addr_humanize(&config.olympus_dao)?.to_string(),
-                    fee_tiers,
-                    fee_in_payout,
+                msg: to_binary(&CustomTreasuryExecuteMsg::WhitelistBond {
+                    bond: bond.clone(),
+                    whitelist: true,
                 })?,
             }
             .into(),
-            reply_on: ReplyOn::Success,
-        }))
-}
+        );
+        messages.push(propose_treasury_policy(
+            deps.as_ref(),
+            &custom_treasury,
+            &pending_bond_info.initial_owner,
+        )?);
+    }
 
-fn register_bond(deps: DepsMut, bond: String) -> StdResult<Response> {
-    let temp_bond_info = read_temp_bond_info(deps.storage)?;
+    if let Some(subsidy_controller) = pending_bond_info.subsidy_controller {
+        let config = read_config(deps.storage)?;
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: deps.api.addr_humanize(&config.subsidy_router)?.to_string(),
+                funds: vec![],
+                msg: to_binary(&SubsidyRouterExecuteMsg::AddSubsidyController {
+                    subsidy_controller: deps.api.addr_humanize(&subsidy_controller)?.to_string(),
+                    bond: bond.clone(),
+                })?,
+            }
+            .into(),
+        );
+    }
 
     store_new_bond_info(
         deps.storage,
         &BondInfo {
-            principal_token: temp_bond_info.principal_token,
-            custom_treasury: temp_bond_info.custom_treasury.unwrap(),
+            principal_token: pending_bond_info.principal_token,
+            custom_treasury,
             bond: deps.api.addr_canonicalize(&bond)?,
-            initial_owner: temp_bond_info.initial_owner,
-            fee_tiers: temp_bond_info.fee_tiers,
+            initial_owner: pending_bond_info.initial_owner,
+            fee_tiers: pending_bond_info.fee_tiers,
+            fee_in_payout: pending_bond_info.fee_in_payout,
+            fee_recipient: None,
         },
     )?;
 
-    remove_temp_bond_info(deps.storage);
+    remove_pending_bond_info(deps.storage, creation_id);
 
-    Ok(Response::default())
+    Ok(Response::new().add_messages(messages))
+}
+
+/// Keeps the creation as failed, handing a treasury created for it to its initial owner
+fn fail_creation(
+    deps: DepsMut,
+    creation_id: u64,
+    mut pending_bond_info: PendingBondInfo,
+    error: String,
+) -> Result<Response, ContractError> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let (true, Some(custom_treasury)) = (
+        pending_bond_info.created_treasury,
+        &pending_bond_info.custom_treasury,
+    ) {
+        messages.push(propose_treasury_policy(
+            deps.as_ref(),
+            custom_treasury,
+            &pending_bond_info.initial_owner,
+        )?);
+    }
+
+    pending_bond_info.status = CreationStatus::Failed;
+    pending_bond_info.error = Some(error.clone());
+    store_pending_bond_info(deps.storage, creation_id, &pending_bond_info)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "create_bond_failed"),
+        attr("creation_id", creation_id.to_string()),
+        attr("error", error),
+    ]))
+}
+
+fn propose_treasury_policy(
+    deps: Deps,
+    custom_treasury: &CanonicalAddr,
+    initial_owner: &CanonicalAddr,
+) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: deps.api.addr_humanize(custom_treasury)?.to_string(),
+        funds: vec![],
+        msg: to_binary(&CustomTreasuryExecuteMsg::ProposeNewPolicy {
+            policy: deps.api.addr_humanize(initial_owner)?.to_string(),
+            expires: None,
+        })?,
+    }
+    .into())
 }
@@ -0,0 +1,36 @@
+use cosmwasm_std::StdError;
+use olympus_pro::{ownership::OwnershipError, utils::FeeTierError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    FeeTier(#[from] FeeTierError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("only olympus dao")]
+    OnlyOlympusDao {},
+
+    #[error("factory must be the subsidy router policy to register a subsidy controller")]
+    NotSubsidyRouterPolicy {},
+
+    #[error("creation {creation_id} has not failed")]
+    CreationNotFailed { creation_id: u64 },
+
+    #[error("invalid reply id: {id}")]
+    InvalidReplyId { id: u64 },
+
+    #[error("missing instantiate data")]
+    MissingInstantiateData {},
+
+    #[error("failed to parse instantiate data")]
+    InvalidInstantiateData {},
+}
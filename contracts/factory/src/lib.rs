@@ -1,6 +1,6 @@
 pub mod contract;
+pub mod error;
 mod query;
-mod response;
 mod state;
 
 #[cfg(test)]
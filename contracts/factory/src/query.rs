@@ -1,7 +1,17 @@
 use cosmwasm_std::{Deps, StdResult};
 
-use crate::state::{read_bond_info, read_config, read_state, State};
-use olympus_pro::factory::{BondInfoResponse, ConfigResponse};
+use crate::state::{
+    read_bond_ids_by_custom_treasury, read_bond_ids_by_initial_owner,
+    read_bond_ids_by_principal_token, read_bond_info, read_bond_infos, read_config,
+    read_pending_bond_infos, read_state, BondInfo, State,
+};
+use olympus_pro::{
+    custom_bond::{
+        ConfigResponse as CustomBondConfigResponse, FeeRecipient, QueryMsg as CustomBondQueryMsg,
+    },
+    factory::{BondInfoResponse, ConfigResponse, FeeRecipientResponse, PendingCreationResponse},
+};
+use terraswap::asset::AssetInfo;
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = read_config(deps.storage)?;
@@ -27,7 +37,139 @@ pub fn query_state(deps: Deps) -> StdResult<State> {
 pub fn query_bond_info(deps: Deps, bond_id: u64) -> StdResult<BondInfoResponse> {
     let bond_info = read_bond_info(deps.storage, bond_id)?;
 
-    let resp = BondInfoResponse {
+    bond_info_to_response(deps, bond_id, bond_info)
+}
+
+pub fn query_fee_recipient(deps: Deps, bond_id: u64) -> StdResult<FeeRecipientResponse> {
+    let bond_info = read_bond_info(deps.storage, bond_id)?;
+
+    let bond = deps.api.addr_humanize(&bond_info.bond)?.to_string();
+
+    // the weighted recipients take the whole fee over the olympus treasury when set
+    let bond_config: CustomBondConfigResponse = deps
+        .querier
+        .query_wasm_smart(bond.clone(), &CustomBondQueryMsg::Config {})?;
+    let fee_recipients = if bond_config.fee_recipients.is_empty() {
+        vec![FeeRecipient {
+            address: bond_config.olympus_treasury,
+            weight: 1,
+        }]
+    } else {
+        bond_config.fee_recipients
+    };
+
+    Ok(FeeRecipientResponse {
+        bond_id,
+        bond,
+        fee_recipients,
+        fee_recipient_override: match bond_info.fee_recipient {
+            Some(fee_recipient) => Some(deps.api.addr_humanize(&fee_recipient)?.to_string()),
+            None => None,
+        },
+    })
+}
+
+pub fn query_bonds(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BondInfoResponse>> {
+    read_bond_infos(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(bond_id, bond_info)| bond_info_to_response(deps, bond_id, bond_info))
+        .collect()
+}
+
+pub fn query_bonds_by_principal_token(
+    deps: Deps,
+    principal_token: AssetInfo,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BondInfoResponse>> {
+    let bond_ids = read_bond_ids_by_principal_token(
+        deps.storage,
+        &principal_token.to_raw(deps.api)?,
+        start_after,
+        limit,
+    )?;
+
+    query_bond_ids(deps, bond_ids)
+}
+
+pub fn query_bonds_by_custom_treasury(
+    deps: Deps,
+    custom_treasury: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BondInfoResponse>> {
+    let bond_ids = read_bond_ids_by_custom_treasury(
+        deps.storage,
+        &deps.api.addr_canonicalize(&custom_treasury)?,
+        start_after,
+        limit,
+    )?;
+
+    query_bond_ids(deps, bond_ids)
+}
+
+pub fn query_bonds_by_initial_owner(
+    deps: Deps,
+    initial_owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BondInfoResponse>> {
+    let bond_ids = read_bond_ids_by_initial_owner(
+        deps.storage,
+        &deps.api.addr_canonicalize(&initial_owner)?,
+        start_after,
+        limit,
+    )?;
+
+    query_bond_ids(deps, bond_ids)
+}
+
+pub fn query_pending_creations(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingCreationResponse>> {
+    read_pending_bond_infos(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(creation_id, pending_bond_info)| {
+            Ok(PendingCreationResponse {
+                creation_id,
+                principal_token: pending_bond_info.principal_token.to_normal(deps.api)?,
+                custom_treasury: match pending_bond_info.custom_treasury {
+                    Some(custom_treasury) => {
+                        Some(deps.api.addr_humanize(&custom_treasury)?.to_string())
+                    }
+                    None => None,
+                },
+                initial_owner: deps
+                    .api
+                    .addr_humanize(&pending_bond_info.initial_owner)?
+                    .to_string(),
+                status: pending_bond_info.status,
+                error: pending_bond_info.error,
+            })
+        })
+        .collect()
+}
+
+fn query_bond_ids(deps: Deps, bond_ids: Vec<u64>) -> StdResult<Vec<BondInfoResponse>> {
+    bond_ids
+        .into_iter()
+        .map(|bond_id| query_bond_info(deps, bond_id))
+        .collect()
+}
+
+fn bond_info_to_response(
+    deps: Deps,
+    bond_id: u64,
+    bond_info: BondInfo,
+) -> StdResult<BondInfoResponse> {
+    Ok(BondInfoResponse {
+        bond_id,
         principal_token: bond_info.principal_token.to_normal(deps.api)?,
         custom_treasury: deps
             .api
@@ -39,7 +181,6 @@ pub fn query_bond_info(deps: Deps, bond_id: u64) -> StdResult<BondInfoResponse>
             .addr_humanize(&bond_info.initial_owner)?
             .to_string(),
         fee_tiers: bond_info.fee_tiers,
-    };
-
-    Ok(resp)
+        fee_in_payout: bond_info.fee_in_payout,
+    })
 }
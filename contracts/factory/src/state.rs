@@ -1,16 +1,25 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
-use cosmwasm_storage::{bucket, bucket_read, singleton, singleton_read, Singleton};
+use cosmwasm_std::{CanonicalAddr, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket};
 
-use olympus_pro::custom_bond::FeeTier;
+use olympus_pro::{
+    custom_bond::{FeeTier, Terms},
+    factory::CreationStatus,
+};
 use terraswap::asset::AssetInfoRaw;
 
 const KEY_CONFIG: &[u8] = b"config";
-const KEY_TEMP_BOND_INFO: &[u8] = b"temp_bond_info";
 const KEY_STATE: &[u8] = b"state";
 const PREFIX_KEY_BOND_INFO: &[u8] = b"prefix_bond_info";
+const PREFIX_KEY_PENDING_BOND_INFO: &[u8] = b"pending_bond_info";
+const PREFIX_KEY_BOND_BY_PRINCIPAL_TOKEN: &[u8] = b"bond_by_principal_token";
+const PREFIX_KEY_BOND_BY_CUSTOM_TREASURY: &[u8] = b"bond_by_custom_treasury";
+const PREFIX_KEY_BOND_BY_INITIAL_OWNER: &[u8] = b"bond_by_initial_owner";
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -25,6 +34,7 @@ pub struct Config {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub bond_length: u64,
+    pub creation_length: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -34,15 +44,24 @@ pub struct BondInfo {
     pub bond: CanonicalAddr,
     pub initial_owner: CanonicalAddr,
     pub fee_tiers: Vec<FeeTier>,
+    pub fee_in_payout: bool,
+    pub fee_recipient: Option<CanonicalAddr>,
 }
 
+/// A bond creation between `execute` and its replies, kept after a failure to be queried
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct TempBondInfo {
+pub struct PendingBondInfo {
     pub principal_token: AssetInfoRaw,
     pub custom_treasury: Option<CanonicalAddr>,
     pub initial_owner: CanonicalAddr,
     pub fee_tiers: Vec<FeeTier>,
     pub fee_in_payout: bool,
+    pub created_treasury: bool,
+    pub subsidy_controller: Option<CanonicalAddr>,
+    pub terms: Option<Terms>,
+    pub initial_debt: Option<Uint128>,
+    pub status: CreationStatus,
+    pub error: Option<String>,
 }
 
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
@@ -61,32 +80,242 @@ pub fn read_state(storage: &dyn Storage) -> StdResult<State> {
     Ok(singleton_read(storage, KEY_STATE).load()?)
 }
 
-pub fn store_temp_bond_info(
+/// Saves a new bond creation and returns its id, which its submessages reply with
+pub fn store_new_pending_bond_info(
+    storage: &mut dyn Storage,
+    pending_bond_info: &PendingBondInfo,
+) -> StdResult<u64> {
+    let mut state = read_state(storage)?;
+    let creation_id = state.creation_length;
+
+    store_pending_bond_info(storage, creation_id, pending_bond_info)?;
+
+    state.creation_length += 1;
+    store_state(storage, &state)?;
+
+    Ok(creation_id)
+}
+
+pub fn store_pending_bond_info(
     storage: &mut dyn Storage,
-    temp_bond_info: &TempBondInfo,
+    creation_id: u64,
+    pending_bond_info: &PendingBondInfo,
 ) -> StdResult<()> {
-    singleton(storage, KEY_TEMP_BOND_INFO).save(temp_bond_info)
+    bucket(storage, PREFIX_KEY_PENDING_BOND_INFO)
+        .save(&creation_id.to_be_bytes(), pending_bond_info)
 }
 
-pub fn read_temp_bond_info(storage: &dyn Storage) -> StdResult<TempBondInfo> {
-    Ok(singleton_read(storage, KEY_TEMP_BOND_INFO).load()?)
+pub fn read_pending_bond_info(
+    storage: &dyn Storage,
+    creation_id: u64,
+) -> StdResult<Option<PendingBondInfo>> {
+    bucket_read(storage, PREFIX_KEY_PENDING_BOND_INFO).may_load(&creation_id.to_be_bytes())
 }
 
-pub fn remove_temp_bond_info(storage: &mut dyn Storage) {
-    let mut store: Singleton<TempBondInfo> = singleton(storage, KEY_TEMP_BOND_INFO);
-    store.remove();
+pub fn read_pending_bond_infos(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, PendingBondInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match range_start(start_after) {
+        Some(start) => start,
+        None => return Ok(vec![]),
+    };
+
+    let pending_bucket: ReadonlyBucket<PendingBondInfo> =
+        bucket_read(storage, PREFIX_KEY_PENDING_BOND_INFO);
+    pending_bucket
+        .range(
+            start.as_ref().map(|start| &start[..]),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (key, pending_bond_info) = item?;
+            Ok((id_from_key(&key), pending_bond_info))
+        })
+        .collect()
+}
+
+pub fn remove_pending_bond_info(storage: &mut dyn Storage, creation_id: u64) {
+    let mut pending_bucket: Bucket<PendingBondInfo> = bucket(storage, PREFIX_KEY_PENDING_BOND_INFO);
+    pending_bucket.remove(&creation_id.to_be_bytes());
 }
 
+/// Saves the bond under the next id and indexes it by principal token, custom treasury and
+/// initial owner
 pub fn store_new_bond_info(storage: &mut dyn Storage, bond_info: &BondInfo) -> StdResult<()> {
     let mut state = read_state(storage)?;
+    let bond_id = state.bond_length.to_be_bytes();
 
-    bucket(storage, PREFIX_KEY_BOND_INFO).save(&state.bond_length.to_be_bytes(), bond_info)?;
+    bucket(storage, PREFIX_KEY_BOND_INFO).save(&bond_id, bond_info)?;
+
+    let (kind, principal_token) = principal_token_key(&bond_info.principal_token);
+    Bucket::multilevel(
+        storage,
+        &[PREFIX_KEY_BOND_BY_PRINCIPAL_TOKEN, kind, principal_token],
+    )
+    .save(&bond_id, &true)?;
+    Bucket::multilevel(
+        storage,
+        &[
+            PREFIX_KEY_BOND_BY_CUSTOM_TREASURY,
+            bond_info.custom_treasury.as_slice(),
+        ],
+    )
+    .save(&bond_id, &true)?;
+    Bucket::multilevel(
+        storage,
+        &[
+            PREFIX_KEY_BOND_BY_INITIAL_OWNER,
+            bond_info.initial_owner.as_slice(),
+        ],
+    )
+    .save(&bond_id, &true)?;
 
     state.bond_length += 1;
 
     store_state(storage, &state)
 }
 
+/// Overwrites a registered bond, its indexed fields must stay unchanged
+pub fn store_bond_info(
+    storage: &mut dyn Storage,
+    bond_id: u64,
+    bond_info: &BondInfo,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_KEY_BOND_INFO).save(&bond_id.to_be_bytes(), bond_info)
+}
+
 pub fn read_bond_info(storage: &dyn Storage, bond_id: u64) -> StdResult<BondInfo> {
     bucket_read(storage, PREFIX_KEY_BOND_INFO).load(&bond_id.to_be_bytes())
 }
+
+pub fn read_bond_infos(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, BondInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match range_start(start_after) {
+        Some(start) => start,
+        None => return Ok(vec![]),
+    };
+
+    let bond_bucket: ReadonlyBucket<BondInfo> = bucket_read(storage, PREFIX_KEY_BOND_INFO);
+    bond_bucket
+        .range(
+            start.as_ref().map(|start| &start[..]),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (key, bond_info) = item?;
+            Ok((id_from_key(&key), bond_info))
+        })
+        .collect()
+}
+
+pub fn read_bond_ids_by_principal_token(
+    storage: &dyn Storage,
+    principal_token: &AssetInfoRaw,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let (kind, principal_token) = principal_token_key(principal_token);
+    read_indexed_bond_ids(
+        ReadonlyBucket::multilevel(
+            storage,
+            &[PREFIX_KEY_BOND_BY_PRINCIPAL_TOKEN, kind, principal_token],
+        ),
+        start_after,
+        limit,
+    )
+}
+
+pub fn read_bond_ids_by_custom_treasury(
+    storage: &dyn Storage,
+    custom_treasury: &CanonicalAddr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    read_indexed_bond_ids(
+        ReadonlyBucket::multilevel(
+            storage,
+            &[
+                PREFIX_KEY_BOND_BY_CUSTOM_TREASURY,
+                custom_treasury.as_slice(),
+            ],
+        ),
+        start_after,
+        limit,
+    )
+}
+
+pub fn read_bond_ids_by_initial_owner(
+    storage: &dyn Storage,
+    initial_owner: &CanonicalAddr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    read_indexed_bond_ids(
+        ReadonlyBucket::multilevel(
+            storage,
+            &[PREFIX_KEY_BOND_BY_INITIAL_OWNER, initial_owner.as_slice()],
+        ),
+        start_after,
+        limit,
+    )
+}
+
+fn read_indexed_bond_ids(
+    index: ReadonlyBucket<bool>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match range_start(start_after) {
+        Some(start) => start,
+        None => return Ok(vec![]),
+    };
+
+    index
+        .range(
+            start.as_ref().map(|start| &start[..]),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            Ok(id_from_key(&key))
+        })
+        .collect()
+}
+
+/// Splits native denoms and token addresses so they can never share an index key
+fn principal_token_key(principal_token: &AssetInfoRaw) -> (&[u8], &[u8]) {
+    match principal_token {
+        AssetInfoRaw::NativeToken { denom } => (b"native", denom.as_bytes()),
+        AssetInfoRaw::Token { contract_addr } => (b"token", contract_addr.as_slice()),
+    }
+}
+
+fn id_from_key(key: &[u8]) -> u64 {
+    let mut bond_id = [0u8; 8];
+    bond_id.copy_from_slice(key);
+    u64::from_be_bytes(bond_id)
+}
+
+/// Range start for ids after `start_after`, `None` when nothing can come after the largest id
+fn range_start(start_after: Option<u64>) -> Option<Option<[u8; 8]>> {
+    match start_after {
+        Some(start_after) => start_after
+            .checked_add(1)
+            .map(|start| Some(start.to_be_bytes())),
+        None => Some(None),
+    }
+}
@@ -1,13 +1,25 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, ContractResult, Decimal, Reply, ReplyOn, StdError, SubMsg,
+    attr, from_binary, to_binary, ContractResult, CosmosMsg, Decimal, Reply, ReplyOn, SubMsg,
     SubMsgExecutionResponse, Uint128, WasmMsg,
 };
 
 use olympus_pro::{
-    custom_bond::{FeeTier, InstantiateMsg as CustomBondInstantiateMsg},
-    custom_treasury::InstantiateMsg as CustomTreasuryInstantiateMsg,
-    factory::{BondInfoResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+    custom_bond::{
+        ExecuteMsg as CustomBondExecuteMsg, FeeRecipient, FeeTier,
+        InstantiateMsg as CustomBondInstantiateMsg, Terms, VestingKind,
+    },
+    custom_treasury::{
+        ExecuteMsg as CustomTreasuryExecuteMsg, InstantiateMsg as CustomTreasuryInstantiateMsg,
+    },
+    factory::{
+        BondInfoResponse, ConfigResponse, ExecuteMsg, FeeRecipientResponse, InstantiateMsg,
+        QueryMsg,
+    },
+    ownership::{OwnershipError, ProposalResponse},
+    response::MsgInstantiateContractResponse,
+    subsidy_router::ExecuteMsg as SubsidyRouterExecuteMsg,
+    utils::FeeTierError,
 };
 
 use protobuf::Message;
@@ -15,9 +27,12 @@ use terraswap::asset::AssetInfo;
 
 use crate::{
     contract::{execute, instantiate, query, reply},
-    response::MsgInstantiateContractResponse,
+    error::ContractError,
     state::State,
-    tests::test_utils::instantiate_factory,
+    tests::{
+        mock_querier::mock_dependencies as custom_mock_dependencies,
+        test_utils::{create_bond, instantiate_factory},
+    },
 };
 
 #[test]
@@ -54,7 +69,13 @@ fn test_initialization() {
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
     let state: State = from_binary(&res).unwrap();
-    assert_eq!(State { bond_length: 0 }, state);
+    assert_eq!(
+        State {
+            bond_length: 0,
+            creation_length: 0
+        },
+        state
+    );
 }
 
 #[test]
@@ -67,11 +88,10 @@ fn test_update_config_fails_if_unauthorized() {
     let msg = ExecuteMsg::UpdateConfig {
         custom_bond_id: Some(3),
         custom_treasury_id: Some(4),
-        policy: Some(String::from("new_policy")),
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -85,7 +105,6 @@ fn test_update_config_by_policy() {
     let msg = ExecuteMsg::UpdateConfig {
         custom_bond_id: Some(3),
         custom_treasury_id: Some(4),
-        policy: Some(String::from("new_policy")),
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -101,12 +120,81 @@ fn test_update_config_by_policy() {
             treasury: String::from("treasury"),
             subsidy_router: String::from("subsidy_router"),
             olympus_dao: String::from("olympus_dao"),
-            policy: String::from("new_policy"),
+            policy: String::from("policy"),
         },
         config
     );
 }
 
+#[test]
+fn test_propose_new_policy_fails_if_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("addr", &[]);
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_transfer_policy() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "propose_new_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PolicyProposal {}).unwrap();
+    let proposal: Option<ProposalResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        Some(ProposalResponse {
+            proposed: String::from("new_policy"),
+            expires: None,
+        }),
+        proposal
+    );
+
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NotProposed {})
+    );
+
+    let info = mock_info("new_policy", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(String::from("new_policy"), config.policy);
+}
+
 #[test]
 fn test_create_bond_fails_if_unauthorized() {
     let mut deps = mock_dependencies(&[]);
@@ -131,10 +219,12 @@ fn test_create_bond_fails_if_unauthorized() {
             },
         ],
         fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -161,14 +251,19 @@ fn test_create_bond_by_policy() {
             },
         ],
         fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_eq!(res.attributes, vec![attr("action", "create_bond"),]);
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "create_bond"), attr("creation_id", "0")]
+    );
     assert_eq!(
         res.messages,
         vec![SubMsg {
-            id: 2,
+            id: 0,
             gas_limit: None,
             msg: WasmMsg::Instantiate {
                 code_id: 1,
@@ -180,7 +275,7 @@ fn test_create_bond_by_policy() {
                     principal_token: AssetInfo::NativeToken {
                         denom: String::from("principal"),
                     },
-                    olympus_treasury: String::from("custom_treasury"),
+                    olympus_treasury: String::from("treasury"),
                     subsidy_router: String::from("subsidy_router"),
                     initial_owner: String::from("initial_owner"),
                     olympus_dao: String::from("olympus_dao"),
@@ -195,15 +290,143 @@ fn test_create_bond_by_policy() {
                         },
                     ],
                     fee_in_payout: true,
+                    principal_recipient: None,
+                    note_nft_code_id: None,
+                    pauser: None,
+                    price_guard: None,
+                    bonding_calculator: None,
+                    terms_delay: None,
+                    measure_received_balance: None,
+                    terms: None,
+                    initial_debt: None,
+                    fee_recipient_admin: Some(String::from(MOCK_CONTRACT_ADDR)),
                 })
                 .unwrap(),
             }
             .into(),
-            reply_on: ReplyOn::Success,
+            reply_on: ReplyOn::Always,
         }]
     );
 }
 
+#[test]
+fn test_create_bond_and_treasury_initializes_bond_with_terms() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let terms = Terms {
+        control_variable: Decimal::from_ratio(1u128, 10u128),
+        vesting_kind: VestingKind::FixedTerm,
+        vesting_term: 864000,
+        vesting_end: 0,
+        minimum_price: Decimal::percent(15),
+        max_payout: Decimal::from_ratio(1u128, 10000u128),
+        max_debt: Uint128::from(1000000u128),
+    };
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBondAndTreasury {
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        subsidy_controller: None,
+        terms: Some(terms.clone()),
+        initial_debt: Some(Uint128::from(100000u128)),
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let mut treasury_inst_res = MsgInstantiateContractResponse::new();
+    treasury_inst_res.set_contract_address("treasury0".to_string());
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(treasury_inst_res.write_to_bytes().unwrap().into()),
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    let bond_msg: CustomBondInstantiateMsg = match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate { msg, .. }) => from_binary(msg).unwrap(),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+    assert_eq!(Some(terms), bond_msg.terms);
+    assert_eq!(Some(Uint128::from(100000u128)), bond_msg.initial_debt);
+}
+
+#[test]
+fn test_create_bond_fails_with_invalid_fee_tiers() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBond {
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        custom_treasury: String::from("custom_treasury"),
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![
+            FeeTier {
+                tier_ceiling: Uint128::from(2u128),
+                fee_rate: Decimal::percent(3),
+            },
+            FeeTier {
+                tier_ceiling: Uint128::from(2u128),
+                fee_rate: Decimal::percent(4),
+            },
+        ],
+        fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::FeeTier(FeeTierError::UnorderedCeilings {
+            previous: Uint128::from(2u128),
+            ceiling: Uint128::from(2u128),
+        })
+    );
+
+    let msg = ExecuteMsg::CreateBondAndTreasury {
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout_token"),
+        },
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![FeeTier {
+            tier_ceiling: Uint128::from(1u128),
+            fee_rate: Decimal::percent(150),
+        }],
+        fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::FeeTier(FeeTierError::FeeRateTooHigh {
+            fee_rate: Decimal::percent(150),
+        })
+    );
+}
+
 #[test]
 fn test_create_bond_register_bond_on_reply() {
     let mut deps = mock_dependencies(&[]);
@@ -228,6 +451,8 @@ fn test_create_bond_register_bond_on_reply() {
             },
         ],
         fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
     };
 
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -236,23 +461,32 @@ fn test_create_bond_register_bond_on_reply() {
     bond_inst_res.set_contract_address("bond0".to_string());
 
     let reply_msg = Reply {
-        id: 2,
+        id: 0,
         result: ContractResult::Ok(SubMsgExecutionResponse {
             events: vec![],
             data: Some(bond_inst_res.write_to_bytes().unwrap().into()),
         }),
     };
 
-    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    // the existing treasury stays with its owner
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert!(res.messages.is_empty());
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
     let state: State = from_binary(&res).unwrap();
-    assert_eq!(State { bond_length: 1 }, state);
+    assert_eq!(
+        State {
+            bond_length: 1,
+            creation_length: 1
+        },
+        state
+    );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::BondInfo { bond_id: 0 }).unwrap();
     let bond_info: BondInfoResponse = from_binary(&res).unwrap();
     assert_eq!(
         BondInfoResponse {
+            bond_id: 0,
             principal_token: AssetInfo::NativeToken {
                 denom: String::from("principal"),
             },
@@ -269,6 +503,7 @@ fn test_create_bond_register_bond_on_reply() {
                     fee_rate: Decimal::percent(4),
                 },
             ],
+            fee_in_payout: true,
         },
         bond_info
     );
@@ -282,7 +517,9 @@ fn test_create_bond_and_treasury_fails_if_unauthorized() {
 
     let info = mock_info("addr", &[]);
     let msg = ExecuteMsg::CreateBondAndTreasury {
-        payout_token: String::from("payout"),
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
         principal_token: AssetInfo::NativeToken {
             denom: String::from("principal"),
         },
@@ -298,10 +535,13 @@ fn test_create_bond_and_treasury_fails_if_unauthorized() {
             },
         ],
         fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -312,7 +552,9 @@ fn test_create_bond_and_treasury_by_policy() {
 
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::CreateBondAndTreasury {
-        payout_token: String::from("payout"),
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
         principal_token: AssetInfo::NativeToken {
             denom: String::from("principal"),
         },
@@ -328,14 +570,20 @@ fn test_create_bond_and_treasury_by_policy() {
             },
         ],
         fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_eq!(res.attributes, vec![attr("action", "create_treasury"),]);
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "create_treasury"), attr("creation_id", "0")]
+    );
     assert_eq!(
         res.messages,
         vec![SubMsg {
-            id: 1,
+            id: 0,
             gas_limit: None,
             msg: WasmMsg::Instantiate {
                 code_id: 2,
@@ -343,13 +591,15 @@ fn test_create_bond_and_treasury_by_policy() {
                 admin: Some(String::from(MOCK_CONTRACT_ADDR)),
                 label: "OlympusPro Custom Treasury".to_string(),
                 msg: to_binary(&CustomTreasuryInstantiateMsg {
-                    payout_token: String::from("payout"),
-                    initial_owner: String::from("initial_owner"),
+                    payout_token: AssetInfo::Token {
+                        contract_addr: String::from("payout"),
+                    },
+                    initial_owner: String::from(MOCK_CONTRACT_ADDR),
                 })
                 .unwrap(),
             }
             .into(),
-            reply_on: ReplyOn::Success,
+            reply_on: ReplyOn::Always,
         }]
     );
 }
@@ -362,7 +612,9 @@ fn test_create_bond_and_treasury_reqeust_create_bond_on_first_reply() {
 
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::CreateBondAndTreasury {
-        payout_token: String::from("payout"),
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
         principal_token: AssetInfo::NativeToken {
             denom: String::from("principal"),
         },
@@ -378,6 +630,9 @@ fn test_create_bond_and_treasury_reqeust_create_bond_on_first_reply() {
             },
         ],
         fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
     };
 
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -386,7 +641,7 @@ fn test_create_bond_and_treasury_reqeust_create_bond_on_first_reply() {
     treasury_inst_res.set_contract_address("treasury0".to_string());
 
     let reply_msg = Reply {
-        id: 1,
+        id: 0,
         result: ContractResult::Ok(SubMsgExecutionResponse {
             events: vec![],
             data: Some(treasury_inst_res.write_to_bytes().unwrap().into()),
@@ -395,11 +650,14 @@ fn test_create_bond_and_treasury_reqeust_create_bond_on_first_reply() {
 
     let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
 
-    assert_eq!(res.attributes, vec![attr("action", "create_bond"),]);
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "create_bond"), attr("creation_id", "0")]
+    );
     assert_eq!(
         res.messages,
         vec![SubMsg {
-            id: 2,
+            id: 0,
             gas_limit: None,
             msg: WasmMsg::Instantiate {
                 code_id: 1,
@@ -411,7 +669,7 @@ fn test_create_bond_and_treasury_reqeust_create_bond_on_first_reply() {
                     principal_token: AssetInfo::NativeToken {
                         denom: String::from("principal"),
                     },
-                    olympus_treasury: String::from("treasury0"),
+                    olympus_treasury: String::from("treasury"),
                     subsidy_router: String::from("subsidy_router"),
                     initial_owner: String::from("initial_owner"),
                     olympus_dao: String::from("olympus_dao"),
@@ -426,11 +684,21 @@ fn test_create_bond_and_treasury_reqeust_create_bond_on_first_reply() {
                         },
                     ],
                     fee_in_payout: true,
+                    principal_recipient: None,
+                    note_nft_code_id: None,
+                    pauser: None,
+                    price_guard: None,
+                    bonding_calculator: None,
+                    terms_delay: None,
+                    measure_received_balance: None,
+                    terms: None,
+                    initial_debt: None,
+                    fee_recipient_admin: Some(String::from(MOCK_CONTRACT_ADDR)),
                 })
                 .unwrap(),
             }
             .into(),
-            reply_on: ReplyOn::Success,
+            reply_on: ReplyOn::Always,
         }]
     );
 }
@@ -443,7 +711,9 @@ fn test_create_bond_and_treasury_register_bond_on_second_reply() {
 
     let info = mock_info("policy", &[]);
     let msg = ExecuteMsg::CreateBondAndTreasury {
-        payout_token: String::from("payout"),
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
         principal_token: AssetInfo::NativeToken {
             denom: String::from("principal"),
         },
@@ -459,6 +729,9 @@ fn test_create_bond_and_treasury_register_bond_on_second_reply() {
             },
         ],
         fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
     };
 
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -467,7 +740,7 @@ fn test_create_bond_and_treasury_register_bond_on_second_reply() {
     treasury_inst_res.set_contract_address("treasury0".to_string());
 
     let reply_msg = Reply {
-        id: 1,
+        id: 0,
         result: ContractResult::Ok(SubMsgExecutionResponse {
             events: vec![],
             data: Some(treasury_inst_res.write_to_bytes().unwrap().into()),
@@ -480,23 +753,53 @@ fn test_create_bond_and_treasury_register_bond_on_second_reply() {
     bond_inst_res.set_contract_address("bond0".to_string());
 
     let reply_msg = Reply {
-        id: 2,
+        id: 0,
         result: ContractResult::Ok(SubMsgExecutionResponse {
             events: vec![],
             data: Some(bond_inst_res.write_to_bytes().unwrap().into()),
         }),
     };
 
-    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("treasury0"),
+                funds: vec![],
+                msg: to_binary(&CustomTreasuryExecuteMsg::WhitelistBond {
+                    bond: String::from("bond0"),
+                    whitelist: true,
+                })
+                .unwrap(),
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("treasury0"),
+                funds: vec![],
+                msg: to_binary(&CustomTreasuryExecuteMsg::ProposeNewPolicy {
+                    policy: String::from("initial_owner"),
+                    expires: None,
+                })
+                .unwrap(),
+            }),
+        ]
+    );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
     let state: State = from_binary(&res).unwrap();
-    assert_eq!(State { bond_length: 1 }, state);
+    assert_eq!(
+        State {
+            bond_length: 1,
+            creation_length: 1
+        },
+        state
+    );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::BondInfo { bond_id: 0 }).unwrap();
     let bond_info: BondInfoResponse = from_binary(&res).unwrap();
     assert_eq!(
         BondInfoResponse {
+            bond_id: 0,
             principal_token: AssetInfo::NativeToken {
                 denom: String::from("principal"),
             },
@@ -513,7 +816,263 @@ fn test_create_bond_and_treasury_register_bond_on_second_reply() {
                     fee_rate: Decimal::percent(4),
                 },
             ],
+            fee_in_payout: true,
         },
         bond_info
     );
 }
+
+#[test]
+fn test_create_bond_and_treasury_fails_if_not_subsidy_router_policy() {
+    let mut deps = custom_mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    deps.querier
+        .with_subsidy_router_policy("subsidy_router", "policy");
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBondAndTreasury {
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        subsidy_controller: Some(String::from("subsidy_controller")),
+        terms: None,
+        initial_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::NotSubsidyRouterPolicy {});
+}
+
+#[test]
+fn test_create_bond_and_treasury_registers_subsidy_controller() {
+    let mut deps = custom_mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    deps.querier
+        .with_subsidy_router_policy("subsidy_router", MOCK_CONTRACT_ADDR);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBondAndTreasury {
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        subsidy_controller: Some(String::from("subsidy_controller")),
+        terms: None,
+        initial_debt: None,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let mut treasury_inst_res = MsgInstantiateContractResponse::new();
+    treasury_inst_res.set_contract_address("treasury0".to_string());
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(treasury_inst_res.write_to_bytes().unwrap().into()),
+        }),
+    };
+
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+    let mut bond_inst_res = MsgInstantiateContractResponse::new();
+    bond_inst_res.set_contract_address("bond0".to_string());
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(bond_inst_res.write_to_bytes().unwrap().into()),
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("subsidy_router"),
+            funds: vec![],
+            msg: to_binary(&SubsidyRouterExecuteMsg::AddSubsidyController {
+                subsidy_controller: String::from("subsidy_controller"),
+                bond: String::from("bond0"),
+            })
+            .unwrap(),
+        })
+    );
+}
+
+#[test]
+fn test_update_bond_fee_recipient_fails_if_not_olympus_dao() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_bond(
+        &mut deps,
+        AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        "custom_treasury",
+        "initial_owner",
+        "bond",
+    );
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::UpdateBondFeeRecipient {
+        bond_id: 0,
+        fee_recipient: Some(String::from("partner_treasury")),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::OnlyOlympusDao {});
+}
+
+#[test]
+fn test_update_bond_fee_recipient_by_olympus_dao() {
+    let mut deps = custom_mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_bond(
+        &mut deps,
+        AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        "custom_treasury",
+        "initial_owner",
+        "bond",
+    );
+    deps.querier.with_olympus_treasury("bond", "treasury");
+
+    let info = mock_info("olympus_dao", &[]);
+    let msg = ExecuteMsg::UpdateBondFeeRecipient {
+        bond_id: 0,
+        fee_recipient: Some(String::from("partner_treasury")),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("bond"),
+            funds: vec![],
+            msg: to_binary(&CustomBondExecuteMsg::UpdateOlympusTreasury {
+                olympus_treasury: String::from("partner_treasury"),
+            })
+            .unwrap(),
+        })]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "update_bond_fee_recipient"),
+            attr("bond_id", "0"),
+            attr("fee_recipient", "partner_treasury"),
+        ]
+    );
+
+    // the bond executed the update
+    deps.querier
+        .with_olympus_treasury("bond", "partner_treasury");
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::FeeRecipient { bond_id: 0 },
+    )
+    .unwrap();
+    let fee_recipient: FeeRecipientResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        FeeRecipientResponse {
+            bond_id: 0,
+            bond: String::from("bond"),
+            fee_recipients: vec![FeeRecipient {
+                address: String::from("partner_treasury"),
+                weight: 1,
+            }],
+            fee_recipient_override: Some(String::from("partner_treasury")),
+        },
+        fee_recipient
+    );
+
+    // clearing the override repoints the bond to the factory treasury
+    let msg = ExecuteMsg::UpdateBondFeeRecipient {
+        bond_id: 0,
+        fee_recipient: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("bond"),
+            funds: vec![],
+            msg: to_binary(&CustomBondExecuteMsg::UpdateOlympusTreasury {
+                olympus_treasury: String::from("treasury"),
+            })
+            .unwrap(),
+        })
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::FeeRecipient { bond_id: 0 },
+    )
+    .unwrap();
+    let fee_recipient: FeeRecipientResponse = from_binary(&res).unwrap();
+    assert_eq!(None, fee_recipient.fee_recipient_override);
+}
+
+#[test]
+fn test_query_fee_recipient_reports_weighted_recipients() {
+    let mut deps = custom_mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_bond(
+        &mut deps,
+        AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        "custom_treasury",
+        "initial_owner",
+        "bond",
+    );
+
+    // the olympus treasury gets nothing once the bond splits the fee
+    let fee_recipients = vec![
+        FeeRecipient {
+            address: String::from("dao_treasury"),
+            weight: 3,
+        },
+        FeeRecipient {
+            address: String::from("partner"),
+            weight: 1,
+        },
+    ];
+    deps.querier.with_olympus_treasury("bond", "treasury");
+    deps.querier
+        .with_fee_recipients("bond", fee_recipients.clone());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::FeeRecipient { bond_id: 0 },
+    )
+    .unwrap();
+    let fee_recipient: FeeRecipientResponse = from_binary(&res).unwrap();
+    assert_eq!(fee_recipients, fee_recipient.fee_recipients);
+}
@@ -0,0 +1,145 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, WasmQuery,
+};
+use std::collections::HashMap;
+
+use olympus_pro::{
+    bonding_calculator::BondingCalculator,
+    custom_bond::{
+        ConfigResponse as CustomBondConfigResponse, FeeRecipient, QueryMsg as CustomBondQueryMsg,
+    },
+    subsidy_router::{
+        ConfigResponse as SubsidyRouterConfigResponse, QueryMsg as SubsidyRouterQueryMsg,
+    },
+};
+use terraswap::asset::AssetInfo;
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    // bond -> olympus treasury the bond pays
+    olympus_treasuries: HashMap<String, String>,
+    fee_recipients: HashMap<String, Vec<FeeRecipient>>,
+    // subsidy router -> policy
+    subsidy_router_policies: HashMap<String, String>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if let Some(policy) = self.subsidy_router_policies.get(contract_addr) {
+                    return match from_binary(msg).unwrap() {
+                        SubsidyRouterQueryMsg::Config {} => SystemResult::Ok(ContractResult::from(
+                            to_binary(&SubsidyRouterConfigResponse {
+                                policy: policy.clone(),
+                            }),
+                        )),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    };
+                }
+
+                let olympus_treasury = match self.olympus_treasuries.get(contract_addr) {
+                    Some(olympus_treasury) => olympus_treasury.clone(),
+                    None => {
+                        return SystemResult::Err(SystemError::NoSuchContract {
+                            addr: contract_addr.clone(),
+                        })
+                    }
+                };
+
+                match from_binary(msg).unwrap() {
+                    CustomBondQueryMsg::Config {} => SystemResult::Ok(ContractResult::from(
+                        to_binary(&CustomBondConfigResponse {
+                            custom_treasury: String::from("custom_treasury"),
+                            payout_token: AssetInfo::Token {
+                                contract_addr: String::from("payout_token"),
+                            },
+                            principal_token: AssetInfo::NativeToken {
+                                denom: String::from("principal"),
+                            },
+                            olympus_treasury,
+                            subsidy_router: String::from("subsidy_router"),
+                            policy: String::from("initial_owner"),
+                            olympus_dao: String::from("olympus_dao"),
+                            fee_tiers: vec![],
+                            fee_in_payout: false,
+                            principal_recipient: String::from("custom_treasury"),
+                            note_nft: None,
+                            pauser: String::from("initial_owner"),
+                            price_guard: None,
+                            bonding_calculator: BondingCalculator::Reserve {},
+                            terms_delay: 0,
+                            fee_recipients: self
+                                .fee_recipients
+                                .get(contract_addr)
+                                .cloned()
+                                .unwrap_or_default(),
+                            referrer_share: Decimal::zero(),
+                            measure_received_balance: false,
+                            fee_recipient_admin: Some(String::from(MOCK_CONTRACT_ADDR)),
+                        }),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            olympus_treasuries: HashMap::new(),
+            fee_recipients: HashMap::new(),
+            subsidy_router_policies: HashMap::new(),
+        }
+    }
+
+    pub fn with_olympus_treasury(&mut self, bond: &str, olympus_treasury: &str) {
+        self.olympus_treasuries
+            .insert(bond.to_string(), olympus_treasury.to_string());
+    }
+
+    pub fn with_fee_recipients(&mut self, bond: &str, fee_recipients: Vec<FeeRecipient>) {
+        self.fee_recipients.insert(bond.to_string(), fee_recipients);
+    }
+
+    pub fn with_subsidy_router_policy(&mut self, subsidy_router: &str, policy: &str) {
+        self.subsidy_router_policies
+            .insert(subsidy_router.to_string(), policy.to_string());
+    }
+}
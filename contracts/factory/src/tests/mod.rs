@@ -1,2 +1,5 @@
 pub mod contract_test;
+pub mod mock_querier;
+pub mod query_test;
+pub mod reply_test;
 pub mod test_utils;
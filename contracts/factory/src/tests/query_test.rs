@@ -0,0 +1,218 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{from_binary, OwnedDeps};
+
+use olympus_pro::factory::{BondInfoResponse, PendingCreationResponse, QueryMsg};
+use terraswap::asset::AssetInfo;
+
+use crate::{
+    contract::query,
+    tests::test_utils::{create_bond, instantiate_factory},
+};
+
+#[test]
+fn test_query_bonds_paginates_by_id() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_registry(&mut deps);
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::Bonds {
+            start_after: None,
+            limit: Some(2),
+        },
+    );
+    assert_eq!(vec![0, 1], bond_ids(&bonds));
+    assert_eq!(
+        BondInfoResponse {
+            bond_id: 1,
+            principal_token: AssetInfo::Token {
+                contract_addr: String::from("principal"),
+            },
+            custom_treasury: String::from("treasury0"),
+            bond: String::from("bond1"),
+            initial_owner: String::from("bob"),
+            fee_tiers: vec![],
+            fee_in_payout: false,
+        },
+        bonds[1]
+    );
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::Bonds {
+            start_after: Some(1),
+            limit: Some(2),
+        },
+    );
+    assert_eq!(vec![2, 3], bond_ids(&bonds));
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::Bonds {
+            start_after: Some(3),
+            limit: None,
+        },
+    );
+    assert_eq!(Vec::<u64>::new(), bond_ids(&bonds));
+}
+
+#[test]
+fn test_query_bonds_by_principal_token() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_registry(&mut deps);
+
+    // the native denom and the token address share the same name
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByPrincipalToken {
+            principal_token: AssetInfo::NativeToken {
+                denom: String::from("principal"),
+            },
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(vec![0, 2], bond_ids(&bonds));
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByPrincipalToken {
+            principal_token: AssetInfo::Token {
+                contract_addr: String::from("principal"),
+            },
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(vec![1, 3], bond_ids(&bonds));
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByPrincipalToken {
+            principal_token: AssetInfo::Token {
+                contract_addr: String::from("principal"),
+            },
+            start_after: Some(1),
+            limit: Some(1),
+        },
+    );
+    assert_eq!(vec![3], bond_ids(&bonds));
+}
+
+#[test]
+fn test_query_bonds_by_custom_treasury_and_initial_owner() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_registry(&mut deps);
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByCustomTreasury {
+            custom_treasury: String::from("treasury0"),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(vec![0, 1, 2], bond_ids(&bonds));
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByCustomTreasury {
+            custom_treasury: String::from("treasury0"),
+            start_after: Some(0),
+            limit: Some(1),
+        },
+    );
+    assert_eq!(vec![1], bond_ids(&bonds));
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByInitialOwner {
+            initial_owner: String::from("alice"),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(vec![0, 2, 3], bond_ids(&bonds));
+    assert_eq!(String::from("bond3"), bonds[2].bond);
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByInitialOwner {
+            initial_owner: String::from("carol"),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(Vec::<u64>::new(), bond_ids(&bonds));
+}
+
+#[test]
+fn test_query_start_after_largest_id_is_empty() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+    create_registry(&mut deps);
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::Bonds {
+            start_after: Some(u64::MAX),
+            limit: None,
+        },
+    );
+    assert_eq!(Vec::<u64>::new(), bond_ids(&bonds));
+
+    let bonds = query_bonds(
+        &deps,
+        QueryMsg::BondsByCustomTreasury {
+            custom_treasury: String::from("treasury0"),
+            start_after: Some(u64::MAX),
+            limit: None,
+        },
+    );
+    assert_eq!(Vec::<u64>::new(), bond_ids(&bonds));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PendingCreations {
+            start_after: Some(u64::MAX),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let pending_creations: Vec<PendingCreationResponse> = from_binary(&res).unwrap();
+    assert!(pending_creations.is_empty());
+}
+
+fn create_registry(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    let native = AssetInfo::NativeToken {
+        denom: String::from("principal"),
+    };
+    let token = AssetInfo::Token {
+        contract_addr: String::from("principal"),
+    };
+
+    create_bond(deps, native.clone(), "treasury0", "alice", "bond0");
+    create_bond(deps, token.clone(), "treasury0", "bob", "bond1");
+    create_bond(deps, native, "treasury0", "alice", "bond2");
+    create_bond(deps, token, "treasury1", "alice", "bond3");
+}
+
+fn query_bonds(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    msg: QueryMsg,
+) -> Vec<BondInfoResponse> {
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn bond_ids(bonds: &[BondInfoResponse]) -> Vec<u64> {
+    bonds.iter().map(|bond| bond.bond_id).collect()
+}
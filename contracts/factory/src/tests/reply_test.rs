@@ -0,0 +1,356 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Binary, ContractResult, OwnedDeps, Reply, SubMsg,
+    SubMsgExecutionResponse, WasmMsg,
+};
+
+use olympus_pro::{
+    custom_treasury::ExecuteMsg as CustomTreasuryExecuteMsg,
+    factory::{BondInfoResponse, CreationStatus, ExecuteMsg, PendingCreationResponse, QueryMsg},
+    response::MsgInstantiateContractResponse,
+};
+
+use protobuf::Message;
+use terraswap::asset::AssetInfo;
+
+use crate::{
+    contract::{execute, query, reply},
+    error::ContractError,
+    tests::test_utils::{create_bond, instantiate_factory},
+};
+
+#[test]
+fn test_treasury_instantiate_failure_keeps_failed_creation() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBondAndTreasury {
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Err(String::from("treasury failed")),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "create_bond_failed"),
+            attr("creation_id", "0"),
+            attr("error", "treasury failed"),
+        ]
+    );
+
+    assert_eq!(
+        vec![PendingCreationResponse {
+            creation_id: 0,
+            principal_token: AssetInfo::NativeToken {
+                denom: String::from("principal"),
+            },
+            custom_treasury: None,
+            initial_owner: String::from("initial_owner"),
+            status: CreationStatus::Failed,
+            error: Some(String::from("treasury failed")),
+        }],
+        query_pending_creations(&deps)
+    );
+
+    // the failed creation can no longer be replied to
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(instantiate_data("treasury0")),
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidReplyId { id: 0 });
+}
+
+#[test]
+fn test_clear_failed_creation() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBond {
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        custom_treasury: String::from("custom_treasury"),
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // a pending creation cannot be cleared
+    let msg = ExecuteMsg::ClearFailedCreation { creation_id: 0 };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::CreationNotFailed { creation_id: 0 });
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Err(String::from("bond failed")),
+    };
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "clear_failed_creation"),
+            attr("creation_id", "0"),
+        ]
+    );
+    assert!(query_pending_creations(&deps).is_empty());
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::CreationNotFailed { creation_id: 0 });
+}
+
+#[test]
+fn test_bond_instantiate_failure_returns_treasury_to_initial_owner() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBondAndTreasury {
+        payout_token: AssetInfo::Token {
+            contract_addr: String::from("payout"),
+        },
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        subsidy_controller: None,
+        terms: None,
+        initial_debt: None,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(instantiate_data("treasury0")),
+        }),
+    };
+
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+    let pending_creations = query_pending_creations(&deps);
+    assert_eq!(CreationStatus::PendingBond, pending_creations[0].status);
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Err(String::from("bond failed")),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("treasury0"),
+            funds: vec![],
+            msg: to_binary(&CustomTreasuryExecuteMsg::ProposeNewPolicy {
+                policy: String::from("initial_owner"),
+                expires: None,
+            })
+            .unwrap(),
+        })]
+    );
+
+    assert_eq!(
+        vec![PendingCreationResponse {
+            creation_id: 0,
+            principal_token: AssetInfo::NativeToken {
+                denom: String::from("principal"),
+            },
+            custom_treasury: Some(String::from("treasury0")),
+            initial_owner: String::from("initial_owner"),
+            status: CreationStatus::Failed,
+            error: Some(String::from("bond failed")),
+        }],
+        query_pending_creations(&deps)
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Bonds {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let bonds: Vec<BondInfoResponse> = from_binary(&res).unwrap();
+    assert!(bonds.is_empty());
+}
+
+#[test]
+fn test_create_bond_instantiate_failure_keeps_failed_creation() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    // a completed creation is cleaned up
+    create_bond(
+        &mut deps,
+        AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        "custom_treasury",
+        "initial_owner",
+        "bond0",
+    );
+    assert!(query_pending_creations(&deps).is_empty());
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBond {
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        custom_treasury: String::from("custom_treasury"),
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(1, res.messages[0].id);
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Err(String::from("bond failed")),
+    };
+
+    // the existing treasury is not the factory's to hand over
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert!(res.messages.is_empty());
+
+    let pending_creations = query_pending_creations(&deps);
+    assert_eq!(1, pending_creations.len());
+    assert_eq!(1, pending_creations[0].creation_id);
+    assert_eq!(
+        Some(String::from("custom_treasury")),
+        pending_creations[0].custom_treasury
+    );
+    assert_eq!(CreationStatus::Failed, pending_creations[0].status);
+}
+
+#[test]
+fn test_reply_fails_with_invalid_instantiate_data() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_factory(&mut deps);
+
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBond {
+        principal_token: AssetInfo::NativeToken {
+            denom: String::from("principal"),
+        },
+        custom_treasury: String::from("custom_treasury"),
+        initial_owner: String::from("initial_owner"),
+        fee_tiers: vec![],
+        fee_in_payout: true,
+        terms: None,
+        initial_debt: None,
+    };
+
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap_err();
+    assert_eq!(res, ContractError::MissingInstantiateData {});
+
+    let reply_msg = Reply {
+        id: 0,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(Binary::from(vec![0xffu8, 0xff])),
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidInstantiateData {});
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(instantiate_data("bond0")),
+        }),
+    };
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidReplyId { id: 1 });
+}
+
+fn instantiate_data(contract_addr: &str) -> Binary {
+    let mut inst_res = MsgInstantiateContractResponse::new();
+    inst_res.set_contract_address(contract_addr.to_string());
+
+    inst_res.write_to_bytes().unwrap().into()
+}
+
+fn query_pending_creations(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> Vec<PendingCreationResponse> {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PendingCreations {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
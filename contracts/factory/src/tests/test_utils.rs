@@ -1,10 +1,15 @@
-use crate::contract::instantiate;
-use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
-use cosmwasm_std::OwnedDeps;
+use crate::contract::{execute, instantiate, reply};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{ContractResult, OwnedDeps, Querier, Reply, SubMsgExecutionResponse};
 
-use olympus_pro::factory::InstantiateMsg;
+use olympus_pro::{
+    factory::{ExecuteMsg, InstantiateMsg},
+    response::MsgInstantiateContractResponse,
+};
+use protobuf::Message;
+use terraswap::asset::AssetInfo;
 
-pub fn instantiate_factory(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+pub fn instantiate_factory<Q: Querier>(deps: &mut OwnedDeps<MockStorage, MockApi, Q>) {
     let msg = InstantiateMsg {
         custom_bond_id: 1,
         custom_treasury_id: 2,
@@ -17,3 +22,38 @@ pub fn instantiate_factory(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerie
 
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 }
+
+/// Creates a bond for an existing treasury and registers it as `bond`
+pub fn create_bond<Q: Querier>(
+    deps: &mut OwnedDeps<MockStorage, MockApi, Q>,
+    principal_token: AssetInfo,
+    custom_treasury: &str,
+    initial_owner: &str,
+    bond: &str,
+) {
+    let info = mock_info("policy", &[]);
+    let msg = ExecuteMsg::CreateBond {
+        principal_token,
+        custom_treasury: String::from(custom_treasury),
+        initial_owner: String::from(initial_owner),
+        fee_tiers: vec![],
+        fee_in_payout: false,
+        terms: None,
+        initial_debt: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let mut bond_inst_res = MsgInstantiateContractResponse::new();
+    bond_inst_res.set_contract_address(bond.to_string());
+
+    let reply_msg = Reply {
+        id: res.messages[0].id,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(bond_inst_res.write_to_bytes().unwrap().into()),
+        }),
+    };
+
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+}
@@ -0,0 +1,92 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{attr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+
+use cw721_base::{ContractError, Cw721Contract, ExecuteMsg as Cw721ExecuteMsg};
+use olympus_pro::{
+    custom_bond::BondInfo,
+    note_nft::{ExecuteMsg, InstantiateMsg, QueryMsg},
+};
+
+type NoteNft<'a> = Cw721Contract<'a, BondInfo, Empty>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    NoteNft::default().instantiate(deps, env, info, msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let tract = NoteNft::default();
+    let msg = match msg {
+        ExecuteMsg::Burn { token_id } => return burn(deps, info, tract, token_id),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => Cw721ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        },
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => Cw721ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        },
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => Cw721ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        },
+        ExecuteMsg::Revoke { spender, token_id } => Cw721ExecuteMsg::Revoke { spender, token_id },
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            Cw721ExecuteMsg::ApproveAll { operator, expires }
+        }
+        ExecuteMsg::RevokeAll { operator } => Cw721ExecuteMsg::RevokeAll { operator },
+        ExecuteMsg::Mint(msg) => Cw721ExecuteMsg::Mint(msg),
+    };
+
+    tract.execute(deps, env, info, msg)
+}
+
+/// Only the minter burns, an owner burning its token would strand the note in the bond
+pub fn burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    tract: NoteNft,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    if tract.minter.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    tract.tokens.load(deps.storage, &token_id)?;
+    tract.tokens.remove(deps.storage, &token_id)?;
+    let token_count = tract.token_count(deps.storage)?;
+    tract.token_count.save(deps.storage, &(token_count - 1))?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "burn"), attr("token_id", token_id)]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    NoteNft::default().query(deps, env, msg)
+}
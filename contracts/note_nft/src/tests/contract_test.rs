@@ -0,0 +1,91 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, Decimal, OwnedDeps, Uint128};
+
+use cw721::{NftInfoResponse, NumTokensResponse};
+use cw721_base::ContractError;
+use olympus_pro::{
+    custom_bond::BondInfo,
+    note_nft::{ExecuteMsg, InstantiateMsg, MintMsg, QueryMsg},
+};
+
+use crate::contract::{execute, instantiate, query};
+
+fn bond_info() -> BondInfo {
+    BondInfo {
+        payout: Uint128::from(1000u128),
+        vesting: 100,
+        last_time: 1_000_000,
+        true_price_paid: Decimal::percent(150),
+    }
+}
+
+fn instantiate_and_mint(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    let msg = InstantiateMsg {
+        name: String::from("note"),
+        symbol: String::from("NOTE"),
+        minter: String::from("bond"),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("bond", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Mint(MintMsg {
+        token_id: String::from("1"),
+        owner: String::from("addr0000"),
+        token_uri: None,
+        extension: bond_info(),
+    });
+    execute(deps.as_mut(), mock_env(), mock_info("bond", &[]), msg).unwrap();
+}
+
+#[test]
+fn test_nft_info_returns_note() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_and_mint(&mut deps);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NftInfo {
+            token_id: String::from("1"),
+        },
+    )
+    .unwrap();
+    let nft_info: NftInfoResponse<BondInfo> = from_binary(&res).unwrap();
+    assert_eq!(nft_info.extension, bond_info());
+}
+
+#[test]
+fn test_burn() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_and_mint(&mut deps);
+
+    let msg = ExecuteMsg::Burn {
+        token_id: String::from("1"),
+    };
+
+    // the owner cannot burn
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    match res {
+        Err(ContractError::Unauthorized {}) => (),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let info = mock_info("bond", &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::OwnerOf {
+            token_id: String::from("1"),
+            include_expired: None,
+        },
+    );
+    assert!(res.is_err());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::NumTokens {}).unwrap();
+    let num_tokens: NumTokensResponse = from_binary(&res).unwrap();
+    assert_eq!(num_tokens.count, 0);
+}
@@ -0,0 +1 @@
+pub mod contract_test;
@@ -3,7 +3,7 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     attr, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, QueryRequest, Response,
-    StdError, StdResult, WasmMsg, WasmQuery,
+    StdResult, WasmMsg, WasmQuery,
 };
 
 use olympus_pro::{
@@ -11,9 +11,11 @@ use olympus_pro::{
         ExecuteMsg as CustomBondExecuteMsg, QueryMsg as CustomBondQueryMsg,
         State as CustomBondState,
     },
+    ownership::{accept, cancel, propose, query_proposal, KEY_POLICY_PROPOSAL},
     subsidy_router::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
 };
 
+use crate::error::ContractError;
 use crate::query::{query_bond, query_config};
 use crate::state::{
     read_config, read_subsidy_controller,
@@ -27,7 +29,7 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     store_config(
         deps.storage,
         &Config {
@@ -41,16 +43,20 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::PaySubsidy {} => pay_subsidy(deps, info),
+        ExecuteMsg::AcceptPolicy {} => accept_policy(deps, env, info),
         _ => {
             assert_policy_privilege(deps.as_ref(), info)?;
             match msg {
-                ExecuteMsg::UpdateConfig { policy } => update_config(deps, policy),
+                ExecuteMsg::ProposeNewPolicy { policy, expires } => {
+                    propose_new_policy(deps, env, policy, expires)
+                }
+                ExecuteMsg::CancelProposal {} => cancel_proposal(deps),
                 ExecuteMsg::AddSubsidyController {
                     subsidy_controller,
                     bond,
@@ -71,23 +77,24 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::BondForController { subsidy_controller } => {
             to_binary(&query_bond(deps, subsidy_controller)?)
         }
+        QueryMsg::PolicyProposal {} => to_binary(&query_proposal(deps, KEY_POLICY_PROPOSAL)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     Ok(Response::default())
 }
 
-fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> StdResult<()> {
+fn assert_policy_privilege(deps: Deps, info: MessageInfo) -> Result<(), ContractError> {
     if read_config(deps.storage)?.policy != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     Ok(())
 }
 
-fn pay_subsidy(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+fn pay_subsidy(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
     let bond = read_subsidy_controller(
         deps.storage,
         &deps.api.addr_canonicalize(info.sender.as_str())?,
@@ -115,7 +122,7 @@ fn add_subsidy_controller(
     deps: DepsMut,
     subsidy_controller: String,
     bond: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     store_subsidy_controller(
         deps.storage,
         &deps.api.addr_canonicalize(&subsidy_controller)?,
@@ -129,7 +136,10 @@ fn add_subsidy_controller(
     ]))
 }
 
-fn remove_subsidy_controller(deps: DepsMut, subsidy_controller: String) -> StdResult<Response> {
+fn remove_subsidy_controller(
+    deps: DepsMut,
+    subsidy_controller: String,
+) -> Result<Response, ContractError> {
     remove_subsidy_controller_state(
         deps.storage,
         deps.api.addr_canonicalize(&subsidy_controller)?,
@@ -141,14 +151,39 @@ fn remove_subsidy_controller(deps: DepsMut, subsidy_controller: String) -> StdRe
     ]))
 }
 
-fn update_config(deps: DepsMut, policy: Option<String>) -> StdResult<Response> {
-    let mut config = read_config(deps.storage)?;
+fn propose_new_policy(
+    deps: DepsMut,
+    env: Env,
+    policy: String,
+    expires: Option<u64>,
+) -> Result<Response, ContractError> {
+    propose(deps, &env, KEY_POLICY_PROPOSAL, &policy, expires)?;
 
-    if let Some(policy) = policy {
-        config.policy = deps.api.addr_canonicalize(&policy)?;
-    }
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_policy"),
+        attr("policy", policy),
+    ]))
+}
+
+fn accept_policy(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let policy = accept(deps.branch(), &env, KEY_POLICY_PROPOSAL, &info.sender)?;
 
+    let mut config = read_config(deps.storage)?;
+    config.policy = policy;
     store_config(deps.storage, &config)?;
 
-    Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_policy"),
+        attr("policy", info.sender),
+    ]))
+}
+
+fn cancel_proposal(deps: DepsMut) -> Result<Response, ContractError> {
+    cancel(deps.storage, KEY_POLICY_PROPOSAL)?;
+
+    Ok(Response::new().add_attribute("action", "cancel_proposal"))
 }
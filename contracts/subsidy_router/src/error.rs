@@ -0,0 +1,15 @@
+use cosmwasm_std::StdError;
+use olympus_pro::ownership::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+}
@@ -1,10 +1,14 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{attr, from_binary, StdError};
+use cosmwasm_std::{attr, from_binary};
 
-use olympus_pro::subsidy_router::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use olympus_pro::{
+    ownership::{OwnershipError, ProposalResponse},
+    subsidy_router::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+};
 
 use crate::{
     contract::{execute, instantiate, query},
+    error::ContractError,
     tests::test_utils::instantiate_subsidy_router,
 };
 
@@ -33,33 +37,68 @@ fn test_initialization() {
 }
 
 #[test]
-fn test_update_config_fails_if_unauthorized() {
+fn test_propose_new_policy_fails_if_unauthorized() {
     let mut deps = mock_dependencies(&[]);
 
     instantiate_subsidy_router(&mut deps);
 
     let info = mock_info("addr", &[]);
-    let msg = ExecuteMsg::UpdateConfig {
-        policy: Some(String::from("new_policy")),
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
-fn test_update_config_by_policy() {
+fn test_transfer_policy() {
     let mut deps = mock_dependencies(&[]);
 
     instantiate_subsidy_router(&mut deps);
 
     let info = mock_info("policy", &[]);
-    let msg = ExecuteMsg::UpdateConfig {
-        policy: Some(String::from("new_policy")),
+    let msg = ExecuteMsg::ProposeNewPolicy {
+        policy: String::from("new_policy"),
+        expires: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_eq!(res.attributes, vec![attr("action", "update_config"),]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "propose_new_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PolicyProposal {}).unwrap();
+    let proposal: Option<ProposalResponse> = from_binary(&res).unwrap();
+    assert_eq!(
+        Some(ProposalResponse {
+            proposed: String::from("new_policy"),
+            expires: None,
+        }),
+        proposal
+    );
+
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Ownership(OwnershipError::NotProposed {})
+    );
+
+    let info = mock_info("new_policy", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptPolicy {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_policy"),
+            attr("policy", "new_policy")
+        ]
+    );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
@@ -84,7 +123,7 @@ fn test_add_subsidy_controller_fails_if_unauthorized() {
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(res, StdError::generic_err("unauthorized"));
+    assert_eq!(res, ContractError::Unauthorized {});
 }
 
 #[test]
@@ -0,0 +1,118 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{Api, CanonicalAddr, QuerierWrapper, StdError, StdResult, Uint128};
+use terraswap::asset::Asset;
+use thiserror::Error;
+
+use crate::{
+    querier::{query_decimals, query_pair_pool},
+    utils::get_value_of_token,
+};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum BondingCalculatorError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("pair has no liquidity")]
+    NoLiquidity {},
+
+    #[error("10^{exp} overflows")]
+    DecimalsOverflow { exp: u32 },
+}
+
+/// Values an amount of principal in payout token units
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BondingCalculator {
+    /// One principal unit is worth one payout unit, only the decimals are rescaled
+    Reserve {},
+    /// Terraswap LP tokens of `pair` at their risk-free value, 2 * sqrt(k) / supply
+    TerraswapLp { pair: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BondingCalculatorRaw {
+    Reserve {},
+    TerraswapLp { pair: CanonicalAddr },
+}
+
+impl BondingCalculator {
+    pub fn to_raw(&self, api: &dyn Api) -> StdResult<BondingCalculatorRaw> {
+        match self {
+            BondingCalculator::Reserve {} => Ok(BondingCalculatorRaw::Reserve {}),
+            BondingCalculator::TerraswapLp { pair } => Ok(BondingCalculatorRaw::TerraswapLp {
+                pair: api.addr_canonicalize(pair)?,
+            }),
+        }
+    }
+
+    pub fn valuation(
+        &self,
+        querier: &QuerierWrapper,
+        principal_asset: Asset,
+        payout_decimals: u8,
+        principal_decimals: u8,
+    ) -> Result<Uint128, BondingCalculatorError> {
+        match self {
+            BondingCalculator::Reserve {} => Ok(get_value_of_token(
+                principal_asset,
+                payout_decimals,
+                principal_decimals,
+            )),
+            BondingCalculator::TerraswapLp { pair } => {
+                let pool = query_pair_pool(querier, pair.to_string())?;
+                if pool.total_share.is_zero() {
+                    return Err(BondingCalculatorError::NoLiquidity {});
+                }
+
+                let decimals = query_decimals(querier, &pool.assets[0].info)? as u32
+                    + query_decimals(querier, &pool.assets[1].info)? as u32;
+
+                // k rescaled to payout decimals on both sides
+                let k = (Uint256::from(pool.assets[0].amount)
+                    * Uint256::from(pool.assets[1].amount))
+                .multiply_ratio(pow10(2 * payout_decimals as u32)?, pow10(decimals)?);
+                let total_value = sqrt(k) * Uint256::from(2u64);
+
+                Ok((Uint256::from(principal_asset.amount) * total_value)
+                    .multiply_ratio(Uint256::one(), Uint256::from(pool.total_share))
+                    .into())
+            }
+        }
+    }
+}
+
+impl BondingCalculatorRaw {
+    pub fn to_normal(&self, api: &dyn Api) -> StdResult<BondingCalculator> {
+        match self {
+            BondingCalculatorRaw::Reserve {} => Ok(BondingCalculator::Reserve {}),
+            BondingCalculatorRaw::TerraswapLp { pair } => Ok(BondingCalculator::TerraswapLp {
+                pair: api.addr_humanize(pair)?.to_string(),
+            }),
+        }
+    }
+}
+
+fn pow10(exp: u32) -> Result<Uint256, BondingCalculatorError> {
+    10u128
+        .checked_pow(exp)
+        .map(Uint256::from)
+        .ok_or(BondingCalculatorError::DecimalsOverflow { exp })
+}
+
+/// Babylonian method, rounded down
+fn sqrt(value: Uint256) -> Uint256 {
+    let two = Uint256::from(2u64);
+    let mut result = value;
+    let mut x = value.multiply_ratio(Uint256::one(), two) + Uint256::one();
+    while x < result {
+        result = x;
+        x = (value.multiply_ratio(Uint256::one(), x) + x).multiply_ratio(Uint256::one(), two);
+    }
+
+    result
+}
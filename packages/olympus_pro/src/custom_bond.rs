@@ -5,6 +5,8 @@ use cosmwasm_std::{Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use terraswap::asset::AssetInfo;
 
+use crate::bonding_calculator::BondingCalculator;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub custom_treasury: String,
@@ -15,18 +17,44 @@ pub struct InstantiateMsg {
     pub olympus_dao: String,
     pub fee_tiers: Vec<FeeTier>,
     pub fee_in_payout: bool,
+    /// Receives the bonded principal, defaults to `custom_treasury`
+    pub principal_recipient: Option<String>,
+    /// note_nft code id, when set every note is minted as an NFT and redeemed by its owner. The
+    /// token metadata is the note at mint and is not updated as it vests, the token is burned
+    /// once the note is fully redeemed
+    pub note_nft_code_id: Option<u64>,
+    /// Can pause deposits, defaults to `initial_owner`
+    pub pauser: Option<String>,
+    pub price_guard: Option<PriceGuard>,
+    /// Values the principal, defaults to `Reserve`
+    pub bonding_calculator: Option<BondingCalculator>,
+    /// Seconds a term change waits before it can be applied, defaults to 0 which applies
+    /// changes immediately
+    pub terms_delay: Option<u64>,
+    /// Bonds the principal balance the contract actually gained, for cw20 principals that burn
+    /// or tax on transfer. Deposits must then go through `DepositFrom`
+    pub measure_received_balance: Option<bool>,
+    /// Initializes the bond at creation, as `InitializeBond` would
+    pub terms: Option<Terms>,
+    /// Starting debt for `terms`, defaults to 0
+    pub initial_debt: Option<Uint128>,
+    /// Can repoint `olympus_treasury` besides the olympus dao, the factory sets itself
+    pub fee_recipient_admin: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
+    /// Rejected once terms are set when `terms_delay` is not 0
     InitializeBond {
         terms: Terms,
         initial_debt: Uint128,
     },
     SetBondTerms {
         vesting_term: Option<u64>,
+        /// Expiry of fixed expiry bonds, notes already issued keep their own
+        vesting_end: Option<u64>,
         max_payout: Option<Decimal>,
         max_debt: Option<Uint128>,
     },
@@ -36,18 +64,91 @@ pub enum ExecuteMsg {
         target: Decimal,
         buffer: u64,
     },
-    UpdatePolicy {
+    /// Queued like the other term changes, so lowering the delay is delayed too
+    SetTermsDelay {
+        terms_delay: u64,
+    },
+    /// Applies every queued term change whose activation time has passed, a change that is no
+    /// longer valid is dropped
+    ApplyPendingTerms {},
+    CancelPendingTerms {
+        id: u64,
+    },
+    /// Starts a policy transfer that `policy` completes with `AcceptPolicy`,
+    /// optionally before the `expires` timestamp
+    ProposeNewPolicy {
         policy: String,
+        expires: Option<u64>,
     },
+    AcceptPolicy {},
+    CancelProposal {},
+    /// Starts an olympus dao transfer that `olympus_dao` completes with `AcceptOlympusDao`
+    ProposeNewOlympusDao {
+        olympus_dao: String,
+        expires: Option<u64>,
+    },
+    AcceptOlympusDao {},
+    CancelOlympusDaoProposal {},
+    /// Olympus dao or the fee recipient admin
     UpdateOlympusTreasury {
         olympus_treasury: String,
     },
+    UpdateFeeTiers {
+        fee_tiers: Vec<FeeTier>,
+    },
+    /// Splits the olympus fee left after the referrer share between `fee_recipients` by weight,
+    /// an empty list sends it all to the olympus treasury
+    UpdateFeeRecipients {
+        fee_recipients: Vec<FeeRecipient>,
+        referrer_share: Decimal,
+    },
+    UpdatePauser {
+        pauser: String,
+    },
+    UpdatePriceGuard {
+        price_guard: Option<PriceGuard>,
+    },
+    /// Blocks new deposits, redemptions keep working
+    Pause {},
+    Unpause {},
     PaySubsidy {},
+    SweepPrincipal {},
     Deposit {
         max_price: Decimal,
         depositor: String,
+        /// Fills as much as fits the max payout and debt capacity and refunds the rest
+        #[serde(default)]
+        allow_partial: bool,
+        /// Fails the deposit if the payout is below this amount
+        min_payout: Option<Uint128>,
+        /// Fails the deposit if executed after this timestamp
+        deadline: Option<u64>,
+        /// Receives `referrer_share` of the olympus fee
+        referrer: Option<String>,
+    },
+    /// Pulls `amount` of the cw20 principal with `TransferFrom` and bonds the balance the
+    /// contract received
+    DepositFrom {
+        amount: Uint128,
+        max_price: Decimal,
+        depositor: String,
+        #[serde(default)]
+        allow_partial: bool,
+        min_payout: Option<Uint128>,
+        deadline: Option<u64>,
+        referrer: Option<String>,
+    },
+    /// Allows `operator` to deposit on behalf of the sender, or anyone when `operator` is `None`
+    ApproveDepositor {
+        operator: Option<String>,
+    },
+    RevokeDepositor {
+        operator: Option<String>,
+    },
+    /// Redeems the listed notes of the sender, or every note when `note_ids` is `None`
+    Redeem {
+        note_ids: Option<Vec<u64>>,
     },
-    Redeem {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -56,6 +157,15 @@ pub enum Cw20HookMsg {
     Deposit {
         max_price: Decimal,
         depositor: String,
+        /// Fills as much as fits the max payout and debt capacity and refunds the rest
+        #[serde(default)]
+        allow_partial: bool,
+        /// Fails the deposit if the payout is below this amount
+        min_payout: Option<Uint128>,
+        /// Fails the deposit if executed after this timestamp
+        deadline: Option<u64>,
+        /// Receives `referrer_share` of the olympus fee
+        referrer: Option<String>,
     },
 }
 
@@ -69,17 +179,50 @@ pub enum QueryMsg {
     Config {},
     State {},
     BondPrice {},
-    PayoutFor { value: Uint128 },
+    PayoutFor {
+        value: Uint128,
+    },
     CurrentDebt {},
     CurrentOlympusFee {},
-    BondInfo { user: String },
+    /// Notes of `user`, or the notes whose NFT `user` owns when notes are NFTs
+    BondInfo {
+        user: String,
+    },
+    /// Notes of every depositor, ordered by depositor address then note id. `limit` counts
+    /// notes, continue from the last `user` and its last note id. Holders are depositors, with
+    /// note NFTs the token owner of a note can differ, `BondInfo` lists by token owner
+    AllBondInfos {
+        start_after: Option<String>,
+        start_after_note_id: Option<u64>,
+        limit: Option<u32>,
+    },
+    BondSummary {},
+    Note {
+        note_id: u64,
+    },
+    DepositorApproval {
+        user: String,
+        operator: String,
+    },
+    /// Quotes a deposit `sender` would make for `depositor`
+    SimulateDeposit {
+        amount: Uint128,
+        depositor: String,
+        sender: String,
+    },
+    PolicyProposal {},
+    OlympusDaoProposal {},
+    PendingTerms {},
+    Referrer {
+        referrer: String,
+    },
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub custom_treasury: String,
-    pub payout_token: String,
+    pub payout_token: AssetInfo,
     pub principal_token: AssetInfo,
     pub olympus_treasury: String,
     pub subsidy_router: String,
@@ -87,17 +230,50 @@ pub struct ConfigResponse {
     pub olympus_dao: String,
     pub fee_tiers: Vec<FeeTier>,
     pub fee_in_payout: bool,
+    pub principal_recipient: String,
+    pub note_nft: Option<String>,
+    pub pauser: String,
+    pub price_guard: Option<PriceGuard>,
+    pub bonding_calculator: BondingCalculator,
+    pub terms_delay: u64,
+    pub fee_recipients: Vec<FeeRecipient>,
+    pub referrer_share: Decimal,
+    pub measure_received_balance: bool,
+    pub fee_recipient_admin: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct Terms {
     pub control_variable: Decimal,
+    #[serde(default)]
+    pub vesting_kind: VestingKind,
+    /// Vesting length in seconds for `FixedTerm` bonds
     pub vesting_term: u64,
+    /// Timestamp every note vests toward for `FixedExpiry` bonds
+    #[serde(default)]
+    pub vesting_end: u64,
     pub minimum_price: Decimal,
     pub max_payout: Decimal,
     pub max_debt: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingKind {
+    /// Each note vests over `vesting_term` from its deposit
+    FixedTerm,
+    /// Each note vests until `vesting_end`
+    FixedExpiry,
+}
+
+// deriving it needs `#[default]`, which requires rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for VestingKind {
+    fn default() -> Self {
+        VestingKind::FixedTerm
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct Adjustment {
     pub addition: bool,
@@ -116,6 +292,45 @@ pub struct State {
     pub payout_since_last_subsidy: Uint128,
     pub total_principal_bonded: Uint128,
     pub total_payout_given: Uint128,
+    pub paused: bool,
+}
+
+/// Rejects deposits whose discount to the spot price of the payout/principal terraswap `pair`
+/// falls outside [`min_discount`, `max_discount`]. Not available with the `TerraswapLp` calculator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceGuard {
+    pub pair: String,
+    pub min_discount: Decimal,
+    pub max_discount: Decimal,
+}
+
+/// Term change the policy queued with `SetBondTerms`, `SetAdjustment` or `SetTermsDelay`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TermsChange {
+    BondTerms {
+        vesting_term: Option<u64>,
+        vesting_end: Option<u64>,
+        max_payout: Option<Decimal>,
+        max_debt: Option<Uint128>,
+    },
+    Adjustment {
+        addition: bool,
+        increment: Decimal,
+        target: Decimal,
+        buffer: u64,
+    },
+    TermsDelay {
+        terms_delay: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingTermsResponse {
+    pub id: u64,
+    pub change: TermsChange,
+    /// Timestamp from which `ApplyPendingTerms` applies the change
+    pub activation: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -124,6 +339,26 @@ pub struct FeeTier {
     pub fee_rate: Decimal,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipient {
+    pub address: String,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferrerResponse {
+    pub referrer: String,
+    /// Olympus fee paid to the referrer so far, in the fee token
+    pub total_fees: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurrentOlympusFeeResponse {
+    pub fee_rate: Decimal,
+    /// Index of the active fee tier, `None` when there are no tiers
+    pub tier: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct BondInfo {
     pub payout: Uint128,
@@ -134,6 +369,75 @@ pub struct BondInfo {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct BondInfoResponse {
+    pub note_id: u64,
     pub info: BondInfo,
     pub pending_payout: Uint128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct HolderBondInfosResponse {
+    pub user: String,
+    pub bond_infos: Vec<BondInfoResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BondSummaryResponse {
+    /// Depositors with an unredeemed note, not the note NFT owners
+    pub holder_count: u64,
+    /// Payout still owed on every note, vested or not
+    pub total_payout: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct SimulateDepositResponse {
+    pub payout: Uint128,
+    pub fee: Uint128,
+    /// Bond price including the olympus fee
+    pub true_price: Decimal,
+    /// Timestamp the note would be fully vested at
+    pub expires: u64,
+    /// Debt ratio after the deposit
+    pub debt_ratio: Decimal,
+    /// Checks the deposit would fail, in the order `Deposit` runs them
+    pub failures: Vec<DepositFailure>,
+}
+
+/// A check `Deposit` would fail, each matches the error of the same name
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositFailure {
+    DepositorNotApproved {},
+    AmountIsZero {},
+    DepositsPaused {},
+    BondExpired {},
+    MaxPriceExceeded {
+        price: Decimal,
+        max_price: Decimal,
+    },
+    InvalidPriceGuardPair {},
+    PriceGuardNoLiquidity {},
+    BondPriceAboveMarket {
+        bond_price: Decimal,
+        market_price: Decimal,
+    },
+    DiscountBelowMinimum {
+        bond_price: Decimal,
+        max_price: Decimal,
+    },
+    DiscountAboveMaximum {
+        bond_price: Decimal,
+        min_price: Decimal,
+    },
+    BondTooSmall {
+        payout: Uint128,
+        min: Uint128,
+    },
+    BondTooLarge {
+        payout: Uint128,
+        max: Uint128,
+    },
+    MaxCapacityReached {
+        debt: Uint128,
+        max_debt: Uint128,
+    },
+}
@@ -3,21 +3,36 @@ use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Uint128;
 
-use terraswap::asset::Asset;
+use terraswap::asset::{Asset, AssetInfo};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub payout_token: String,
+    pub payout_token: AssetInfo,
     pub initial_owner: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    UpdateConfig { policy: Option<String> },
-    SendPayoutTokens { amount: Uint128 },
-    Withdraw { asset: Asset, recipient: String },
-    WhitelistBond { bond: String, whitelist: bool },
+    /// Starts a policy transfer that `policy` completes with `AcceptPolicy`,
+    /// optionally before the `expires` timestamp
+    ProposeNewPolicy {
+        policy: String,
+        expires: Option<u64>,
+    },
+    AcceptPolicy {},
+    CancelProposal {},
+    SendPayoutTokens {
+        amount: Uint128,
+    },
+    Withdraw {
+        asset: Asset,
+        recipient: String,
+    },
+    WhitelistBond {
+        bond: String,
+        whitelist: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -29,11 +44,12 @@ pub struct MigrateMsg {}
 pub enum QueryMsg {
     Config {},
     BondWhitelist { bond: String },
+    PolicyProposal {},
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
-    pub payout_token: String,
+    pub payout_token: AssetInfo,
     pub policy: String,
 }
@@ -1,9 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use cosmwasm_std::Uint128;
 use terraswap::asset::AssetInfo;
 
-use crate::custom_bond::FeeTier;
+use crate::custom_bond::{FeeRecipient, FeeTier, Terms};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -20,21 +21,50 @@ pub enum ExecuteMsg {
     UpdateConfig {
         custom_bond_id: Option<u64>,
         custom_treasury_id: Option<u64>,
-        policy: Option<String>,
     },
+    /// Starts a policy transfer that `policy` completes with `AcceptPolicy`,
+    /// optionally before the `expires` timestamp
+    ProposeNewPolicy {
+        policy: String,
+        expires: Option<u64>,
+    },
+    AcceptPolicy {},
+    CancelProposal {},
+    /// Creates a treasury and its bond in one transaction. The treasury whitelists the bond and
+    /// is proposed to `initial_owner`, who takes it over with `AcceptPolicy`. A
+    /// `subsidy_controller` is registered for the bond, which requires the factory to be the
+    /// subsidy router policy. The bond starts with `terms` and `initial_debt` when given
     CreateBondAndTreasury {
-        payout_token: String,
+        payout_token: AssetInfo,
         principal_token: AssetInfo,
         initial_owner: String,
         fee_tiers: Vec<FeeTier>,
         fee_in_payout: bool,
+        subsidy_controller: Option<String>,
+        terms: Option<Terms>,
+        initial_debt: Option<Uint128>,
     },
+    /// Creates a bond for an existing treasury, starting with `terms` and `initial_debt` when
+    /// given
     CreateBond {
         principal_token: AssetInfo,
         custom_treasury: String,
         initial_owner: String,
         fee_tiers: Vec<FeeTier>,
         fee_in_payout: bool,
+        terms: Option<Terms>,
+        initial_debt: Option<Uint128>,
+    },
+    /// Repoints the olympus treasury of `bond_id` to `fee_recipient`, `None` falls back to the
+    /// factory treasury. The factory must be the bond fee recipient admin, which it is for the
+    /// bonds it creates
+    UpdateBondFeeRecipient {
+        bond_id: u64,
+        fee_recipient: Option<String>,
+    },
+    /// Removes the record of a failed bond creation
+    ClearFailedCreation {
+        creation_id: u64,
     },
 }
 
@@ -47,7 +77,39 @@ pub struct MigrateMsg {}
 pub enum QueryMsg {
     Config {},
     State {},
-    BondInfo { bond_id: u64 },
+    BondInfo {
+        bond_id: u64,
+    },
+    /// Bonds ordered by id
+    Bonds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    BondsByPrincipalToken {
+        principal_token: AssetInfo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    BondsByCustomTreasury {
+        custom_treasury: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    BondsByInitialOwner {
+        initial_owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Olympus fee recipients the bond currently pays, along with the recorded override
+    FeeRecipient {
+        bond_id: u64,
+    },
+    /// Bond creations that have not completed, ordered by creation id
+    PendingCreations {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    PolicyProposal {},
 }
 
 // We define a custom struct for each query response
@@ -63,9 +125,42 @@ pub struct ConfigResponse {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BondInfoResponse {
+    pub bond_id: u64,
     pub principal_token: AssetInfo,
     pub custom_treasury: String,
     pub bond: String,
     pub initial_owner: String,
     pub fee_tiers: Vec<FeeTier>,
+    pub fee_in_payout: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipientResponse {
+    pub bond_id: u64,
+    pub bond: String,
+    /// Weighted recipients of the olympus fee left after the referrer share, the bond olympus
+    /// treasury alone when the bond has none
+    pub fee_recipients: Vec<FeeRecipient>,
+    pub fee_recipient_override: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CreationStatus {
+    /// Waiting for the custom treasury instantiation
+    PendingTreasury,
+    /// Waiting for the bond instantiation
+    PendingBond,
+    /// A submessage failed, `custom_treasury` holds the treasury if it was created
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingCreationResponse {
+    pub creation_id: u64,
+    pub principal_token: AssetInfo,
+    pub custom_treasury: Option<String>,
+    pub initial_owner: String,
+    pub status: CreationStatus,
+    pub error: Option<String>,
 }
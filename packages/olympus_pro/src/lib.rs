@@ -1,6 +1,10 @@
+pub mod bonding_calculator;
 pub mod custom_bond;
 pub mod custom_treasury;
 pub mod factory;
+pub mod note_nft;
+pub mod ownership;
 pub mod querier;
+pub mod response;
 pub mod subsidy_router;
 pub mod utils;
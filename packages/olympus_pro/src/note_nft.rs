@@ -0,0 +1,48 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Binary;
+use cw721::Expiration;
+
+use crate::custom_bond::BondInfo;
+
+pub use cw721_base::{InstantiateMsg, QueryMsg};
+
+pub type MintMsg = cw721_base::MintMsg<BondInfo>;
+
+/// cw721-base messages with the note as token metadata, plus `Burn`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    RevokeAll {
+        operator: String,
+    },
+    /// Minter only
+    Mint(MintMsg),
+    /// Minter only, the bond burns the token of a fully redeemed note
+    Burn {
+        token_id: String,
+    },
+}
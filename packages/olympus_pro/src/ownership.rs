@@ -0,0 +1,112 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use cosmwasm_std::{Addr, CanonicalAddr, Deps, DepsMut, Env, StdError, StdResult, Storage};
+use cosmwasm_storage::{singleton, singleton_read};
+
+pub const KEY_POLICY_PROPOSAL: &[u8] = b"policy_proposal";
+
+/// Role transfer waiting to be accepted by `proposed`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposed: CanonicalAddr,
+    pub expires: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub proposed: String,
+    pub expires: Option<u64>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OwnershipError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("proposal expiry {expires} is in the past")]
+    InvalidExpiry { expires: u64 },
+
+    #[error("no pending proposal")]
+    NoPendingProposal {},
+
+    #[error("proposal expired at {expires}")]
+    ProposalExpired { expires: u64 },
+
+    #[error("sender is not the proposed address")]
+    NotProposed {},
+}
+
+/// Stores `proposed` as the pending holder under `key`, replacing any previous proposal.
+/// The caller checks that the sender holds the role.
+pub fn propose(
+    deps: DepsMut,
+    env: &Env,
+    key: &[u8],
+    proposed: &str,
+    expires: Option<u64>,
+) -> Result<(), OwnershipError> {
+    if let Some(expires) = expires {
+        if expires <= env.block.time.seconds() {
+            return Err(OwnershipError::InvalidExpiry { expires });
+        }
+    }
+
+    singleton(deps.storage, key).save(&Proposal {
+        proposed: deps.api.addr_canonicalize(proposed)?,
+        expires,
+    })?;
+
+    Ok(())
+}
+
+/// Consumes the proposal under `key` and returns the new holder, who must be `sender`
+pub fn accept(
+    deps: DepsMut,
+    env: &Env,
+    key: &[u8],
+    sender: &Addr,
+) -> Result<CanonicalAddr, OwnershipError> {
+    let proposal = read_proposal(deps.storage, key)?.ok_or(OwnershipError::NoPendingProposal {})?;
+
+    if proposal.proposed != deps.api.addr_canonicalize(sender.as_str())? {
+        return Err(OwnershipError::NotProposed {});
+    }
+
+    if let Some(expires) = proposal.expires {
+        if env.block.time.seconds() > expires {
+            return Err(OwnershipError::ProposalExpired { expires });
+        }
+    }
+
+    singleton::<Proposal>(deps.storage, key).remove();
+
+    Ok(proposal.proposed)
+}
+
+/// Drops the proposal under `key`. The caller checks that the sender holds the role.
+pub fn cancel(storage: &mut dyn Storage, key: &[u8]) -> Result<(), OwnershipError> {
+    if read_proposal(storage, key)?.is_none() {
+        return Err(OwnershipError::NoPendingProposal {});
+    }
+
+    singleton::<Proposal>(storage, key).remove();
+
+    Ok(())
+}
+
+pub fn read_proposal(storage: &dyn Storage, key: &[u8]) -> StdResult<Option<Proposal>> {
+    singleton_read(storage, key).may_load()
+}
+
+pub fn query_proposal(deps: Deps, key: &[u8]) -> StdResult<Option<ProposalResponse>> {
+    read_proposal(deps.storage, key)?
+        .map(|proposal| {
+            Ok(ProposalResponse {
+                proposed: deps.api.addr_humanize(&proposal.proposed)?.to_string(),
+                expires: proposal.expires,
+            })
+        })
+        .transpose()
+}
@@ -1,7 +1,18 @@
-use cosmwasm_std::{to_binary, QuerierWrapper, QueryRequest, StdResult, Uint128, WasmQuery};
+use cosmwasm_std::{
+    to_binary, to_vec, Binary, ContractResult, Empty, QuerierWrapper, QueryRequest, StdError,
+    StdResult, SystemResult, Uint128, WasmQuery,
+};
+use std::str::FromStr;
 
 use cw20::{Cw20QueryMsg, TokenInfoResponse};
-use terraswap::asset::AssetInfo;
+use protobuf::{
+    wire_format::WireType, CodedInputStream, CodedOutputStream, ProtobufError, ProtobufResult,
+};
+use terraswap::{
+    asset::AssetInfo,
+    asset::PairInfo,
+    pair::{PoolResponse, QueryMsg as PairQueryMsg},
+};
 
 pub fn query_decimals(querier: &QuerierWrapper, asset: &AssetInfo) -> StdResult<u8> {
     match asset {
@@ -12,6 +23,79 @@ pub fn query_decimals(querier: &QuerierWrapper, asset: &AssetInfo) -> StdResult<
     }
 }
 
+pub const BANK_SUPPLY_OF_PATH: &str = "/cosmos.bank.v1beta1.Query/SupplyOf";
+
+pub fn query_supply(querier: &QuerierWrapper, asset: &AssetInfo) -> StdResult<Uint128> {
+    match asset {
+        AssetInfo::NativeToken { denom } => query_native_supply(querier, denom.to_string()),
+        AssetInfo::Token { contract_addr } => {
+            query_token_supply(querier, contract_addr.to_string())
+        }
+    }
+}
+
+/// Queries the bank module through its stargate `SupplyOf` endpoint,
+/// which answers with protobuf instead of json
+pub fn query_native_supply(querier: &QuerierWrapper, denom: String) -> StdResult<Uint128> {
+    let mut data = vec![];
+    {
+        // QuerySupplyOfRequest { string denom = 1 }
+        let mut os = CodedOutputStream::vec(&mut data);
+        os.write_string(1, &denom).map_err(protobuf_err)?;
+        os.flush().map_err(protobuf_err)?;
+    }
+
+    let request: QueryRequest<Empty> = QueryRequest::Stargate {
+        path: BANK_SUPPLY_OF_PATH.to_string(),
+        data: Binary(data),
+    };
+    let res = match querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Err(system_err) => {
+            return Err(StdError::generic_err(format!(
+                "Querier system error: {}",
+                system_err
+            )))
+        }
+        SystemResult::Ok(ContractResult::Err(contract_err)) => {
+            return Err(StdError::generic_err(format!(
+                "Querier contract error: {}",
+                contract_err
+            )))
+        }
+        SystemResult::Ok(ContractResult::Ok(value)) => value,
+    };
+
+    // QuerySupplyOfResponse { Coin amount = 1 }, Coin { string denom = 1; string amount = 2 }
+    let coin = read_length_delimited(res.as_slice(), 1).map_err(protobuf_err)?;
+    let amount = read_length_delimited(&coin, 2).map_err(protobuf_err)?;
+    let amount = String::from_utf8(amount).map_err(|e| StdError::parse_err("Uint128", e))?;
+    if amount.is_empty() {
+        return Ok(Uint128::zero());
+    }
+
+    Uint128::from_str(&amount)
+}
+
+/// Returns the last occurrence of the length delimited `field` of a protobuf message
+fn read_length_delimited(data: &[u8], field: u32) -> ProtobufResult<Vec<u8>> {
+    let mut is = CodedInputStream::from_bytes(data);
+    let mut value = vec![];
+    while !is.eof()? {
+        let (number, wire_type) = is.read_tag_unpack()?;
+        if number == field && wire_type == WireType::WireTypeLengthDelimited {
+            value = is.read_bytes()?;
+        } else {
+            is.skip_field(wire_type)?;
+        }
+    }
+
+    Ok(value)
+}
+
+fn protobuf_err(err: ProtobufError) -> StdError {
+    StdError::parse_err("QuerySupplyOfResponse", err)
+}
+
 pub fn query_token_supply(querier: &QuerierWrapper, contract_addr: String) -> StdResult<Uint128> {
     let res: TokenInfoResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: contract_addr.to_string(),
@@ -29,3 +113,17 @@ pub fn query_token_decimals(querier: &QuerierWrapper, contract_addr: String) ->
 
     Ok(res.decimals)
 }
+
+pub fn query_pair_pool(querier: &QuerierWrapper, pair: String) -> StdResult<PoolResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: pair,
+        msg: to_binary(&PairQueryMsg::Pool {})?,
+    }))
+}
+
+pub fn query_pair_info(querier: &QuerierWrapper, pair: String) -> StdResult<PairInfo> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: pair,
+        msg: to_binary(&PairQueryMsg::Pair {})?,
+    }))
+}
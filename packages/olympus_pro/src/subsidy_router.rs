@@ -9,9 +9,14 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    UpdateConfig {
-        policy: Option<String>,
+    /// Starts a policy transfer that `policy` completes with `AcceptPolicy`,
+    /// optionally before the `expires` timestamp
+    ProposeNewPolicy {
+        policy: String,
+        expires: Option<u64>,
     },
+    AcceptPolicy {},
+    CancelProposal {},
     PaySubsidy {},
     AddSubsidyController {
         subsidy_controller: String,
@@ -31,6 +36,7 @@ pub struct MigrateMsg {}
 pub enum QueryMsg {
     Config {},
     BondForController { subsidy_controller: String },
+    PolicyProposal {},
 }
 
 // We define a custom struct for each query response
@@ -1,5 +1,37 @@
 use cosmwasm_std::{Decimal, Uint128};
 use terraswap::asset::Asset;
+use thiserror::Error;
+
+use crate::custom_bond::FeeTier;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum FeeTierError {
+    #[error("fee tier ceilings must be strictly increasing: {ceiling} after {previous}")]
+    UnorderedCeilings { previous: Uint128, ceiling: Uint128 },
+
+    #[error("fee rate {fee_rate} must be below 100%")]
+    FeeRateTooHigh { fee_rate: Decimal },
+}
+
+/// Tiers apply in order of `tier_ceiling`, the last one also covering everything above it
+pub fn validate_fee_tiers(fee_tiers: &[FeeTier]) -> Result<(), FeeTierError> {
+    for (i, fee_tier) in fee_tiers.iter().enumerate() {
+        if fee_tier.fee_rate >= Decimal::one() {
+            return Err(FeeTierError::FeeRateTooHigh {
+                fee_rate: fee_tier.fee_rate,
+            });
+        }
+
+        if i > 0 && fee_tier.tier_ceiling <= fee_tiers[i - 1].tier_ceiling {
+            return Err(FeeTierError::UnorderedCeilings {
+                previous: fee_tiers[i - 1].tier_ceiling,
+                ceiling: fee_tier.tier_ceiling,
+            });
+        }
+    }
+
+    Ok(())
+}
 
 pub fn get_value_of_token(
     principal_asset: Asset,